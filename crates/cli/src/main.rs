@@ -4,16 +4,19 @@ use std::io::Write;
 use std::mem;
 use std::path::Path;
 
-use anyhow::{anyhow, Context, Result};
-use clap::Parser;
-use lib::database::{Database, Entry, IndexSource};
-use lib::inflection;
-use lib::{Form, Furigana, PartOfSpeech};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Parser, Subcommand};
+use lib::accent;
+use lib::database::{Database, Entry};
+use lib::inflection::{self, Kind};
+use lib::{jmdict, Form, Furigana, PartOfSpeech};
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::EnvFilter;
 
 #[derive(Parser)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
     /// Filter by parts of speech. If no arguments are specified, will filter by
     /// entries which matches all specified parts of speech.
     #[arg(long = "pos", name = "pos")]
@@ -34,7 +37,7 @@ struct Args {
     #[arg(long)]
     any_lang: bool,
     /// Don't print output in furigana.
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_furigana: bool,
     /// Search arguments to filter by. Must be either kana or kanji, which is
     /// matched against entries searched for.
@@ -46,6 +49,23 @@ struct Args {
     /// Only fetch the specified sequence ids.
     #[arg(long = "seq")]
     sequences: Vec<u32>,
+    /// Print information about the database, verify its checksum and exit.
+    #[arg(long)]
+    info: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the full conjugation table of a word.
+    Conjugate {
+        /// The word to conjugate, in kana or kanji. Inflected words conjugate
+        /// their dictionary form.
+        word: String,
+        /// Only include inflections with the given form, such as `past` or
+        /// `te-iru`. Can be specified multiple times.
+        #[arg(long = "form", value_parser = parse_form)]
+        forms: Vec<Form>,
+    },
 }
 
 #[cfg(unix)]
@@ -128,6 +148,31 @@ fn main() -> Result<()> {
 
     let db = Database::new(data.as_ref())?;
 
+    if args.info {
+        let info = db.info()?;
+        let unknown = "unknown";
+
+        println!("Format version: {}", info.version);
+        println!("Checksum: {:08x}", info.checksum);
+        db.verify()?;
+        println!("Checksum verified");
+        println!("Built: {} (unix time)", info.built);
+        println!("JMdict created: {}", info.jmdict_created.unwrap_or(unknown));
+        println!(
+            "Kanjidic2 created: {}",
+            info.kanjidic2_created.unwrap_or(unknown)
+        );
+        println!(
+            "Kanjidic2 version: {}",
+            info.kanjidic2_version.unwrap_or(unknown)
+        );
+        return Ok(());
+    }
+
+    if let Some(Command::Conjugate { word, forms }) = &args.command {
+        return conjugate(&db, word, forms, !args.no_furigana);
+    }
+
     let mut to_look_up = BTreeSet::new();
 
     for &seq in &args.sequences {
@@ -138,10 +183,10 @@ fn main() -> Result<()> {
         let seed = args.sequences.is_empty();
 
         if seed {
-            to_look_up.extend(db.lookup(input)?);
+            to_look_up.extend(db.resolve(input)?);
         } else {
             let filter = db
-                .lookup(input)?
+                .resolve(input)?
                 .into_iter()
                 .map(|id| id.index())
                 .collect::<HashSet<_>>();
@@ -170,15 +215,21 @@ fn main() -> Result<()> {
     let current_lang = args.lang.as_deref().unwrap_or("eng");
 
     for (i, index) in to_look_up.iter().enumerate() {
-        let extra = match index.source() {
-            IndexSource::VerbInflection { inflection, .. } => {
-                Some(format!("Found through verb inflection: {inflection:?}"))
-            }
-            IndexSource::AdjectiveInflection { inflection, .. } => Some(format!(
-                "Found through adjective inflection: {inflection:?}"
-            )),
-            _ => None,
-        };
+        let extra = index.source().explain().map(|explanation| {
+            let what = match explanation.kind {
+                Kind::Verb => "verb inflection",
+                Kind::Adjective => "adjective inflection",
+                Kind::Noun => "する conjugation",
+            };
+
+            let colloquial = if index.source().is_colloquial() {
+                " (colloquial)"
+            } else {
+                ""
+            };
+
+            format!("Found through {what}: {explanation}{colloquial}")
+        });
 
         let Entry::Dict(d) = db.get(*index)? else {
             continue;
@@ -192,6 +243,24 @@ fn main() -> Result<()> {
 
         for (index, reading) in d.reading_elements.iter().enumerate() {
             println!("  #{index} {:?}", reading.debug_sparse());
+
+            if !reading.accent.is_empty() {
+                let accents = reading
+                    .accent
+                    .iter()
+                    .map(|&a| accent::mark(reading.text, a))
+                    .collect::<Vec<_>>();
+
+                println!("     Accent: {}", accents.join(", "));
+            }
+
+            if !args.no_furigana {
+                for a in &reading.furigana {
+                    let furigana =
+                        Furigana::new(a.kanji, reading.text, "").with_alignment(&a.lengths);
+                    println!("     Furigana: {furigana}");
+                }
+            }
         }
 
         for (index, kanji) in d.kanji_elements.iter().enumerate() {
@@ -222,6 +291,19 @@ fn main() -> Result<()> {
             }
         }
 
+        if args.examples {
+            let examples = db.examples_for(d.sequence as u32)?;
+
+            if !examples.is_empty() {
+                println!("  Sentences ({}):", examples.len());
+
+                for s in examples.page(0, 5)? {
+                    println!("  - {}", s.japanese);
+                    println!("    {}", s.english);
+                }
+            }
+        }
+
         if !args.inflection || (to_look_up.len() > 1 && args.sequences.is_empty()) {
             continue;
         }
@@ -234,11 +316,17 @@ fn main() -> Result<()> {
         let stdout = std::io::stdout();
         let mut o = stdout.lock();
 
-        for (_, c, _) in inflection::conjugate(&d) {
+        for (reading, c, _) in inflection::conjugate(&d) {
+            let alignment = alignment(&d, reading);
+
             writeln!(o, "{p}# Inflections:")?;
 
             writeln!(o, "{p}  Dictionary:")?;
-            writeln!(o, "{p}  - {}", dis0(c.dictionary.furigana()))?;
+            writeln!(
+                o,
+                "{p}  - {}",
+                dis0(c.dictionary.furigana().with_alignment(alignment))
+            )?;
 
             for (c, form) in c.inflections {
                 if args.polite != c.contains(Form::Polite) {
@@ -246,7 +334,11 @@ fn main() -> Result<()> {
                 }
 
                 writeln!(o, "{p}  {c:?}:")?;
-                writeln!(o, "{p}  - {}", dis(form.furigana()))?;
+                writeln!(
+                    o,
+                    "{p}  - {}",
+                    dis(form.furigana().with_alignment(alignment))
+                )?;
             }
         }
 
@@ -256,6 +348,88 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Print the conjugation tables of all entries matching the given word.
+fn conjugate(db: &Database<'_>, word: &str, forms: &[Form], do_furigana: bool) -> Result<()> {
+    let stdout = std::io::stdout();
+    let mut o = stdout.lock();
+
+    let mut seen = HashSet::new();
+
+    for id in db.resolve(word)? {
+        if !seen.insert(id.index()) {
+            continue;
+        }
+
+        let Entry::Dict(d) = db.get(id)? else {
+            continue;
+        };
+
+        for (reading, c, kind) in inflection::conjugate(&d) {
+            let alignment = alignment(&d, reading);
+
+            writeln!(
+                o,
+                "#{} {}:",
+                d.sequence,
+                maybe_furigana(
+                    c.dictionary.furigana().with_alignment(alignment),
+                    do_furigana
+                )
+            )?;
+
+            for (inflection, form) in c.iter() {
+                if !forms.iter().all(|&f| inflection.contains(f)) {
+                    continue;
+                }
+
+                let explanation = inflection::explain(*inflection, kind);
+
+                if explanation.steps.is_empty() {
+                    write!(o, "  plain")?;
+                } else {
+                    write!(o, "  {explanation}")?;
+                }
+
+                writeln!(
+                    o,
+                    ": {}",
+                    maybe_furigana(form.furigana().with_alignment(alignment), do_furigana)
+                )?;
+            }
+        }
+    }
+
+    if seen.is_empty() {
+        bail!("No entries found for `{word}`");
+    }
+
+    o.flush()?;
+    Ok(())
+}
+
+/// Get the per-kanji furigana alignment of the reading an inflection table was
+/// generated from.
+fn alignment<'a>(d: &'a jmdict::Entry<'_>, reading: inflection::Reading) -> &'a [u8] {
+    let Some(kanji) = d.kanji_elements.get(usize::from(reading.kanji)) else {
+        return &[];
+    };
+
+    d.reading_elements
+        .get(usize::from(reading.reading))
+        .map(|r| r.alignment(kanji.text))
+        .unwrap_or_default()
+}
+
+/// Parse a form by name, such as `past` or `te-iru`.
+fn parse_form(s: &str) -> Result<Form, String> {
+    let name = s.replace(['-', '_'], "");
+
+    Form::ALL
+        .into_iter()
+        .find(|form| format!("{form:?}").eq_ignore_ascii_case(&name))
+        .ok_or_else(|| format!("unknown form `{s}`"))
+}
+
 fn maybe_furigana<const N: usize, const S: usize>(
     furigana: Furigana<'_, N, S>,
     do_furigana: bool,
@@ -10,9 +10,13 @@ use axum::http::{HeaderValue, Method, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::{Extension, Json};
 use clap::Parser;
-use lib::database::{Database, EntryResultKey};
+use lib::database::{Database, EntryResultKey, Info};
+use lib::inflection::{self, Explanation, Kind, Step};
 use lib::jmdict;
+use lib::jmnedict;
 use lib::kanjidic2;
+use lib::tatoeba;
+use lib::{Furigana, FuriganaGroup, Inflection};
 use serde::{Deserialize, Serialize};
 use tokio::signal::ctrl_c;
 #[cfg(windows)]
@@ -53,6 +57,7 @@ async fn main() -> Result<()> {
 
     tracing::info!("Loading database...");
     let db = lib::database::Database::new(data).context("loading database")?;
+    db.verify().context("verifying database")?;
     tracing::info!("Database loaded");
 
     let cors = CorsLayer::new()
@@ -85,30 +90,56 @@ type RequestResult<T> = std::result::Result<T, RequestError>;
 
 struct RequestError {
     error: anyhow::Error,
+    status: StatusCode,
+}
+
+impl RequestError {
+    /// Construct an error caused by the request, which is reported to the
+    /// client with the given status.
+    fn client(status: StatusCode, message: String) -> Self {
+        Self {
+            error: Error::msg(message),
+            status,
+        }
+    }
 }
 
 impl From<anyhow::Error> for RequestError {
     #[inline]
     fn from(error: anyhow::Error) -> Self {
-        Self { error }
+        Self {
+            error,
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+        }
     }
 }
 
+/// Default number of entries returned by a search.
+const DEFAULT_LIMIT: usize = 100;
+/// The maximum number of entries a client may request from a search.
+const MAX_LIMIT: usize = 1000;
+const DEFAULT_EXAMPLES_LIMIT: usize = 20;
+
 #[derive(Deserialize)]
 struct SearchRequest {
     q: Option<String>,
+    limit: Option<usize>,
 }
 
 #[derive(Serialize)]
 struct SearchEntry {
     key: EntryResultKey,
     entry: jmdict::Entry<'static>,
+    /// Explanations of the inflections through which the entry was found.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    inflections: Vec<Explanation>,
 }
 
 #[derive(Serialize)]
 struct SearchResponse {
     entries: Vec<SearchEntry>,
     characters: Vec<kanjidic2::Character<'static>>,
+    names: Vec<jmnedict::Entry<'static>>,
 }
 
 async fn search(
@@ -121,15 +152,28 @@ async fn search(
 
     let mut entries = Vec::new();
 
-    let search = db.search(q)?;
+    let limit = request.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    let search = db.search(q, limit)?;
 
     for (key, entry) in search.entries {
-        entries.push(SearchEntry { key, entry });
+        let inflections = key
+            .sources
+            .iter()
+            .flat_map(|source| source.explain())
+            .filter(|explanation| !explanation.steps.is_empty())
+            .collect();
+
+        entries.push(SearchEntry {
+            key,
+            entry,
+            inflections,
+        });
     }
 
     Ok(Json(SearchResponse {
         entries,
         characters: search.characters,
+        names: search.names,
     }))
 }
 
@@ -165,11 +209,229 @@ async fn analyze(
     Ok(Json(AnalyzeResponse { data: entries }))
 }
 
+#[derive(Deserialize)]
+struct SegmentRequest {
+    q: String,
+}
+
+#[derive(Serialize)]
+struct SegmentToken {
+    start: usize,
+    end: usize,
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<EntryResultKey>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entry: Option<jmdict::Entry<'static>>,
+}
+
+#[derive(Serialize)]
+struct SegmentResponse {
+    tokens: Vec<SegmentToken>,
+}
+
+async fn segment(
+    Query(request): Query<SegmentRequest>,
+    Extension(db): Extension<Database<'static>>,
+) -> RequestResult<Json<SegmentResponse>> {
+    let mut tokens = Vec::new();
+
+    for token in db.segment(&request.q)? {
+        let (key, entry) = token.entry.unzip();
+
+        tokens.push(SegmentToken {
+            start: token.start,
+            end: token.end,
+            text: request.q[token.start..token.end].to_owned(),
+            key,
+            entry,
+        });
+    }
+
+    Ok(Json(SegmentResponse { tokens }))
+}
+
+#[derive(Deserialize)]
+struct ComponentsRequest {
+    /// Components to search for, where each character is a component.
+    q: String,
+}
+
+#[derive(Serialize)]
+struct ComponentsResponse {
+    characters: Vec<kanjidic2::Character<'static>>,
+}
+
+async fn kanji_by_components(
+    Query(request): Query<ComponentsRequest>,
+    Extension(db): Extension<Database<'static>>,
+) -> RequestResult<Json<ComponentsResponse>> {
+    let mut components = Vec::new();
+
+    for (i, c) in request.q.char_indices() {
+        if !c.is_whitespace() && c != ',' {
+            components.push(&request.q[i..i + c.len_utf8()]);
+        }
+    }
+
+    let characters = db.kanji_by_components(&components)?;
+    Ok(Json(ComponentsResponse { characters }))
+}
+
+#[derive(Deserialize)]
+struct ExamplesRequest {
+    seq: u32,
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct ExamplesResponse {
+    total: usize,
+    examples: Vec<tatoeba::Sentence<'static>>,
+}
+
+async fn examples(
+    Query(request): Query<ExamplesRequest>,
+    Extension(db): Extension<Database<'static>>,
+) -> RequestResult<Json<ExamplesResponse>> {
+    let examples = db.examples_for(request.seq)?;
+
+    let page = examples.page(
+        request.offset.unwrap_or_default(),
+        request.limit.unwrap_or(DEFAULT_EXAMPLES_LIMIT),
+    )?;
+
+    Ok(Json(ExamplesResponse {
+        total: examples.len(),
+        examples: page,
+    }))
+}
+
+#[derive(Deserialize)]
+struct InflectRequest {
+    seq: u32,
+    /// Only include the tables for the given kanji or reading.
+    reading: Option<String>,
+}
+
+#[derive(Serialize)]
+struct InflectForm {
+    inflection: Inflection,
+    steps: Vec<Step>,
+    text: String,
+    reading: String,
+    furigana: Vec<FuriganaGroup<'static>>,
+}
+
+impl InflectForm {
+    fn new<const N: usize, const S: usize>(
+        inflection: Inflection,
+        kind: Kind,
+        furigana: Furigana<'static, N, S>,
+    ) -> Self {
+        Self {
+            inflection,
+            steps: inflection::explain(inflection, kind).steps,
+            text: furigana.kanji().to_string(),
+            reading: furigana.reading().to_string(),
+            furigana: furigana.iter().collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct InflectTable {
+    reading: inflection::Reading,
+    kind: Kind,
+    dictionary: InflectForm,
+    inflections: Vec<InflectForm>,
+    /// Colloquial contractions of the inflections, like 食べてる.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    colloquial: Vec<InflectForm>,
+}
+
+#[derive(Serialize)]
+struct InflectResponse {
+    tables: Vec<InflectTable>,
+}
+
+async fn inflect(
+    Query(request): Query<InflectRequest>,
+    Extension(db): Extension<Database<'static>>,
+) -> RequestResult<Json<InflectResponse>> {
+    let Some(id) = db.lookup_sequence(request.seq)? else {
+        return Err(RequestError::client(
+            StatusCode::NOT_FOUND,
+            format!("Missing entry with sequence {}", request.seq),
+        ));
+    };
+
+    let lib::database::Entry::Dict(entry) = db.get(id)? else {
+        return Err(RequestError::client(
+            StatusCode::BAD_REQUEST,
+            format!("Entry {} is not a dictionary entry", request.seq),
+        ));
+    };
+
+    let mut tables = Vec::new();
+
+    for (reading, inflections, kind) in inflection::conjugate(&entry) {
+        if let Some(expected) = request.reading.as_deref() {
+            let kanji = entry.kanji_elements.get(usize::from(reading.kanji));
+            let kana = entry.reading_elements.get(usize::from(reading.reading));
+
+            if kanji.map(|k| k.text) != Some(expected) && kana.map(|r| r.text) != Some(expected) {
+                continue;
+            }
+        }
+
+        let dictionary = InflectForm::new(
+            Inflection::default(),
+            kind,
+            inflections.dictionary.furigana(),
+        );
+
+        let colloquial = inflections
+            .iter_colloquial()
+            .map(|(inflection, fragments)| {
+                InflectForm::new(*inflection, kind, fragments.furigana())
+            })
+            .collect();
+
+        let inflections = inflections
+            .iter()
+            .map(|(inflection, fragments)| {
+                InflectForm::new(*inflection, kind, fragments.furigana())
+            })
+            .collect();
+
+        tables.push(InflectTable {
+            reading,
+            kind,
+            dictionary,
+            inflections,
+            colloquial,
+        });
+    }
+
+    Ok(Json(InflectResponse { tables }))
+}
+
+async fn info(Extension(db): Extension<Database<'static>>) -> RequestResult<Json<Info<'static>>> {
+    Ok(Json(db.info()?))
+}
+
 impl IntoResponse for RequestError {
     fn into_response(self) -> Response {
-        tracing::error!("{}", self.error);
+        if self.status.is_server_error() {
+            tracing::error!("{}", self.error);
+        } else {
+            tracing::debug!("{}", self.error);
+        }
+
         let mut response = Response::new(boxed(Body::empty()));
-        *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+        *response.status_mut() = self.status;
         response
     }
 }
@@ -282,6 +544,11 @@ mod bundle {
         Router::new()
             .route("/analyze", get(super::analyze))
             .route("/search", get(super::search))
+            .route("/segment", get(super::segment))
+            .route("/kanji/components", get(super::kanji_by_components))
+            .route("/examples", get(super::examples))
+            .route("/inflect", get(super::inflect))
+            .route("/info", get(super::info))
     }
 }
 
@@ -306,6 +573,11 @@ mod bundle {
             .route("/", get(index_handler))
             .route("/api/analyze", get(super::analyze))
             .route("/api/search", get(super::search))
+            .route("/api/segment", get(super::segment))
+            .route("/api/kanji/components", get(super::kanji_by_components))
+            .route("/api/examples", get(super::examples))
+            .route("/api/inflect", get(super::inflect))
+            .route("/api/info", get(super::info))
             .route("/*file", get(static_handler))
             .fallback(index_handler)
     }
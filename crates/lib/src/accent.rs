@@ -0,0 +1,119 @@
+//! Support for pitch accent data.
+//!
+//! Accents are stored as the mora after which the pitch drops, where `0`
+//! indicates that it never drops (heiban).
+
+use anyhow::{bail, Result};
+
+/// The downstep marker used when rendering an accent.
+const DOWNSTEP: char = 'ꜜ';
+
+/// Parse tab-separated accent data, calling `f` with the kanji, reading and
+/// accent positions of each line.
+///
+/// Each line has the form `<kanji>\t<reading>\t<accents>`, where accents are
+/// separated by commas and might be annotated such as `0(名),2`. Kana-only
+/// words might use the reading as their kanji.
+pub fn parse<'a>(input: &'a str, mut f: impl FnMut(&'a str, &'a str, Vec<u8>)) -> Result<()> {
+    for (n, line) in input.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut columns = line.split('\t');
+
+        let (Some(kanji), Some(reading), Some(accents)) =
+            (columns.next(), columns.next(), columns.next())
+        else {
+            bail!("{}: expected `<kanji>\\t<reading>\\t<accents>`", n + 1);
+        };
+
+        let mut output = Vec::new();
+
+        for accent in accents.split(',') {
+            let digits = accent
+                .trim()
+                .split(|c: char| !c.is_ascii_digit())
+                .next()
+                .unwrap_or_default();
+
+            if let Ok(accent) = digits.parse() {
+                output.push(accent);
+            }
+        }
+
+        if !output.is_empty() {
+            f(kanji.trim(), reading.trim(), output);
+        }
+    }
+
+    Ok(())
+}
+
+/// Render the given reading with a downstep marker after the mora where the
+/// pitch drops.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(lib::accent::mark("たべる", 2), "たべꜜる");
+/// assert_eq!(lib::accent::mark("きょう", 1), "きょꜜう");
+/// assert_eq!(lib::accent::mark("はし", 0), "はし");
+/// ```
+pub fn mark(reading: &str, accent: u8) -> String {
+    let mut output = String::with_capacity(reading.len() + DOWNSTEP.len_utf8());
+    let mut mora = 0;
+    let mut it = reading.chars().peekable();
+
+    while let Some(c) = it.next() {
+        output.push(c);
+
+        // Small kana are part of the preceding mora.
+        while let Some(&c) = it.peek().filter(|c| is_small(**c)) {
+            output.push(c);
+            it.next();
+        }
+
+        mora += 1;
+
+        if mora == accent {
+            output.push(DOWNSTEP);
+        }
+    }
+
+    output
+}
+
+fn is_small(c: char) -> bool {
+    "ゃゅょぁぃぅぇぉゎャュョァィゥェォヮ".contains(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let input = "食べる\tたべる\t2\n箸\tはし\t1\n今日\tきょう\t1,0(副)\n";
+
+        let mut output = Vec::new();
+        parse(input, |k, r, a| output.push((k, r, a))).unwrap();
+
+        assert_eq!(
+            output,
+            [
+                ("食べる", "たべる", vec![2]),
+                ("箸", "はし", vec![1]),
+                ("今日", "きょう", vec![1, 0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mark() {
+        assert_eq!(mark("はし", 1), "はꜜし");
+        assert_eq!(mark("はし", 2), "はしꜜ");
+        assert_eq!(mark("がっこう", 0), "がっこう");
+        assert_eq!(mark("シャツ", 1), "シャꜜツ");
+    }
+}
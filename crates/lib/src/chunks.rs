@@ -0,0 +1,130 @@
+//! Helper to incrementally read large XML documents.
+
+use std::io::Read;
+use std::str;
+
+use anyhow::{bail, Result};
+
+/// The number of bytes to read at a time.
+const BLOCK: usize = 1 << 20;
+
+/// Reads a document in chunks, where each chunk ends with one of the given
+/// element end tags.
+///
+/// This relies on the document being made up of a sequence of top level
+/// records, such as `<entry>` elements, so that each chunk can be parsed
+/// separately after the first one.
+pub(crate) struct Chunks<R> {
+    reader: R,
+    ends: &'static [&'static str],
+    buf: String,
+    pending: Vec<u8>,
+    consumed: usize,
+    eof: bool,
+}
+
+impl<R> Chunks<R>
+where
+    R: Read,
+{
+    pub(crate) fn new(reader: R, ends: &'static [&'static str]) -> Self {
+        Self {
+            reader,
+            ends,
+            buf: String::new(),
+            pending: Vec::new(),
+            consumed: 0,
+            eof: false,
+        }
+    }
+
+    /// Get the next chunk, or `None` if the underlying reader is exhausted.
+    pub(crate) fn next(&mut self) -> Result<Option<&str>> {
+        self.buf.drain(..self.consumed);
+        self.consumed = 0;
+
+        loop {
+            let end = self
+                .ends
+                .iter()
+                .filter_map(|end| Some(self.buf.rfind(end)? + end.len()))
+                .max();
+
+            if let Some(end) = end {
+                self.consumed = end;
+                return Ok(Some(&self.buf[..end]));
+            }
+
+            if self.eof {
+                if self.buf.is_empty() {
+                    return Ok(None);
+                }
+
+                self.consumed = self.buf.len();
+                return Ok(Some(&self.buf));
+            }
+
+            self.fill()?;
+        }
+    }
+
+    /// Read another block of input, decoding as much of it as possible.
+    fn fill(&mut self) -> Result<()> {
+        let start = self.pending.len();
+        self.pending.resize(start + BLOCK, 0);
+        let n = self.reader.read(&mut self.pending[start..])?;
+        self.pending.truncate(start + n);
+
+        if n == 0 {
+            if !self.pending.is_empty() {
+                bail!("Input ends with incomplete UTF-8");
+            }
+
+            self.eof = true;
+            return Ok(());
+        }
+
+        // Any incomplete character at the end is kept until the next read.
+        let valid = match str::from_utf8(&self.pending) {
+            Ok(string) => string.len(),
+            Err(error) if error.error_len().is_none() => error.valid_up_to(),
+            Err(error) => return Err(error.into()),
+        };
+
+        self.buf.push_str(str::from_utf8(&self.pending[..valid])?);
+        self.pending.drain(..valid);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read};
+
+    use super::Chunks;
+
+    /// A reader which only produces a single byte at a time.
+    struct Bytewise<'a>(&'a [u8]);
+
+    impl Read for Bytewise<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            (&mut self.0).take(1).read(buf)
+        }
+    }
+
+    #[test]
+    fn test_chunks() {
+        let input = "<root><entry>食べる</entry><entry>a</entry></root>";
+
+        let mut chunks = Chunks::new(Bytewise(input.as_bytes()), &["</entry>"]);
+
+        let mut output = String::new();
+
+        while let Some(chunk) = chunks.next().unwrap() {
+            assert!(chunk.ends_with("</entry>") || chunk == "</root>");
+            output.push_str(chunk);
+        }
+
+        assert_eq!(output, input);
+    }
+}
@@ -28,6 +28,18 @@ impl<'a, const N: usize> Concat<'a, N> {
         }
     }
 
+    /// Strip the given suffix from the last string, if it ends with it.
+    pub fn strip_suffix(&self, suffix: &str) -> Option<Self> {
+        let mut storage = self.storage.clone();
+        let last = storage.pop()?.strip_suffix(suffix)?;
+
+        if !last.is_empty() {
+            storage.push(last);
+        }
+
+        Some(Concat { storage })
+    }
+
     /// Iterate over strings.
     pub fn as_slice(&self) -> &[&'a str] {
         self.storage.as_slice()
@@ -0,0 +1,159 @@
+/// Normalize an english phrase so that it can be matched regardless of case,
+/// leading particles like `to` or `the` and simple inflections such as
+/// plurals or tense.
+///
+/// Such as `To receive (something)` => `receiv someth`.
+pub(super) fn normalize(input: &str) -> String {
+    let input = input.to_lowercase();
+
+    let mut words = input
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .peekable();
+
+    while let Some(&word) = words.peek() {
+        if !matches!(word, "to" | "a" | "an" | "the") {
+            break;
+        }
+
+        words.next();
+    }
+
+    let mut output = String::new();
+
+    for word in words {
+        if !output.is_empty() {
+            output.push(' ');
+        }
+
+        output.push_str(stem(word));
+    }
+
+    output
+}
+
+/// Perform very simple suffix stripping of an english word.
+fn stem(word: &str) -> &str {
+    if word.len() <= 3 || !word.is_ascii() {
+        return word;
+    }
+
+    let word = if let Some(w) = word.strip_suffix("ies") {
+        w
+    } else if let Some(w) = word.strip_suffix("sses") {
+        &word[..w.len() + 2]
+    } else if word.ends_with("ss") || word.ends_with("us") || word.ends_with("is") {
+        word
+    } else if let Some(w) = word.strip_suffix("es").filter(|w| ends_with_sibilant(w)) {
+        w
+    } else if let Some(w) = word.strip_suffix('s') {
+        w
+    } else if let Some(w) = word.strip_suffix("ing").filter(|w| w.len() >= 3) {
+        undouble(w)
+    } else if let Some(w) = word.strip_suffix("ied") {
+        w
+    } else if let Some(w) = word.strip_suffix("ed").filter(|w| w.len() >= 3) {
+        undouble(w)
+    } else {
+        word
+    };
+
+    match word.strip_suffix(['e', 'y']) {
+        Some(w) if w.len() >= 3 => w,
+        _ => word,
+    }
+}
+
+fn ends_with_sibilant(w: &str) -> bool {
+    w.ends_with(['s', 'x', 'z']) || w.ends_with("ch") || w.ends_with("sh")
+}
+
+/// Strip a trailing doubled consonant, like in `running` => `run`.
+fn undouble(w: &str) -> &str {
+    let b = w.as_bytes();
+
+    match b {
+        [.., a, b] if a == b && !matches!(a, b'a' | b'e' | b'i' | b'o' | b'u' | b'l' | b's') => {
+            &w[..w.len() - 1]
+        }
+        _ => w,
+    }
+}
+
+/// The maximum edit distance tolerated for a normalized query of the given
+/// length.
+pub(super) fn max_distance(query: &str) -> usize {
+    match query.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Calculate the edit distance between two strings, counting transpositions
+/// as a single edit. Returns `None` if the distance exceeds `max`.
+pub(super) fn distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut prev2 = vec![0; b.len() + 1];
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        let mut min = current[0];
+
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+
+            current[j] = (prev[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(prev[j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(prev2[j - 2] + 1);
+            }
+
+            min = min.min(current[j]);
+        }
+
+        if min > max {
+            return None;
+        }
+
+        (prev2, prev, current) = (prev, current, prev2);
+    }
+
+    let distance = prev[b.len()];
+    (distance <= max).then_some(distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("To receive"), "receiv");
+        assert_eq!(normalize("received"), "receiv");
+        assert_eq!(normalize("receiving"), "receiv");
+        assert_eq!(normalize("the cats"), "cat");
+        assert_eq!(normalize("boxes"), "box");
+        assert_eq!(normalize("running"), "run");
+        assert_eq!(normalize("to study (something)"), "stud someth");
+        assert_eq!(normalize("studies"), "stud");
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("reciev", "receiv", 1), Some(1));
+        assert_eq!(distance("kitten", "sitting", 3), Some(3));
+        assert_eq!(distance("kitten", "sitting", 2), None);
+        assert_eq!(distance("same", "same", 0), Some(0));
+    }
+}
@@ -0,0 +1,159 @@
+/// A glob pattern used to match keys in the database.
+///
+/// `*` matches any sequence of characters, and `?` matches any single
+/// character. Their full-width variants `＊` and `？` are also accepted since
+/// they are what a Japanese input method produces.
+///
+/// Since `?` and `*` are common in english text, like in "what?", ascii
+/// wildcards are only used if the rest of the pattern is Japanese. Full-width
+/// wildcards explicitly mark a pattern regardless of what it contains.
+pub(super) struct Pattern {
+    chars: Vec<Glob>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Glob {
+    Char(char),
+    Any,
+    Many,
+}
+
+impl Pattern {
+    /// Parse a pattern, returning `None` if the input contains no wildcards
+    /// or if it should not be treated as a pattern.
+    pub(super) fn new(input: &str) -> Option<Self> {
+        let mut chars = Vec::new();
+        let mut wildcard = false;
+        let mut explicit = false;
+        let mut ascii = false;
+
+        for c in input.chars() {
+            let glob = match c {
+                '*' | '＊' => Glob::Many,
+                '?' | '？' => Glob::Any,
+                c => Glob::Char(c),
+            };
+
+            wildcard |= !matches!(glob, Glob::Char(..));
+            explicit |= matches!(c, '＊' | '？');
+            ascii |= matches!(glob, Glob::Char(c) if c.is_ascii() && !c.is_ascii_whitespace());
+
+            // Consecutive `*` are redundant.
+            if glob == Glob::Many && chars.last() == Some(&Glob::Many) {
+                continue;
+            }
+
+            chars.push(glob);
+        }
+
+        if !wildcard || (ascii && !explicit) {
+            return None;
+        }
+
+        Some(Self { chars })
+    }
+
+    /// Test if the pattern starts or ends with a literal character, which is
+    /// needed to search for it without visiting every key.
+    pub(super) fn is_anchored(&self) -> bool {
+        matches!(self.chars.first(), Some(Glob::Char(..)))
+            || matches!(self.chars.last(), Some(Glob::Char(..)))
+    }
+
+    /// The literal prefix of the pattern.
+    pub(super) fn prefix(&self) -> String {
+        self.chars
+            .iter()
+            .map_while(|g| match *g {
+                Glob::Char(c) => Some(c),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The literal suffix of the pattern, in reverse.
+    pub(super) fn reversed_suffix(&self) -> String {
+        self.chars
+            .iter()
+            .rev()
+            .map_while(|g| match *g {
+                Glob::Char(c) => Some(c),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Test if the pattern matches the given text.
+    pub(super) fn is_match(&self, text: &str) -> bool {
+        let text = text.chars().collect::<Vec<_>>();
+
+        let mut p = 0;
+        let mut t = 0;
+        // Position to backtrack to after the last seen `*`.
+        let mut backtrack = None;
+
+        while t < text.len() {
+            match self.chars.get(p) {
+                Some(Glob::Many) => {
+                    backtrack = Some((p, t));
+                    p += 1;
+                    continue;
+                }
+                Some(Glob::Any) => {
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+                Some(Glob::Char(c)) if *c == text[t] => {
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+                _ => {}
+            }
+
+            let Some((bp, bt)) = backtrack else {
+                return false;
+            };
+
+            backtrack = Some((bp, bt + 1));
+            p = bp + 1;
+            t = bt + 1;
+        }
+
+        self.chars[p..].iter().all(|g| *g == Glob::Many)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pattern;
+
+    #[test]
+    fn test_pattern() {
+        assert!(Pattern::new("たべる").is_none());
+        assert!(Pattern::new("what?").is_none());
+        assert!(Pattern::new("*ing").is_none());
+        assert!(Pattern::new("what？").is_some());
+
+        let p = Pattern::new("*かける").unwrap();
+        assert_eq!(p.prefix(), "");
+        assert_eq!(p.reversed_suffix(), "るけか");
+        assert!(p.is_match("かける"));
+        assert!(p.is_match("でかける"));
+        assert!(!p.is_match("かけた"));
+
+        let p = Pattern::new("？ん?").unwrap();
+        assert!(!p.is_anchored());
+        assert!(p.is_match("さんぽ"));
+        assert!(!p.is_match("さんぽする"));
+        assert!(!p.is_match("んぽ"));
+
+        let p = Pattern::new("た*る").unwrap();
+        assert_eq!(p.prefix(), "た");
+        assert!(p.is_anchored());
+        assert!(p.is_match("たべる"));
+        assert!(p.is_match("たる"));
+        assert!(!p.is_match("たべた"));
+    }
+}
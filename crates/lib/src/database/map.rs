@@ -0,0 +1,79 @@
+//! Swiss maps which are allowed to be empty.
+
+use core::borrow::Borrow;
+use core::hash::Hash;
+
+use musli_zerocopy::buf::Visit;
+use musli_zerocopy::{swiss, Buf, Error, OwnedBuf, Ref, ZeroCopy};
+
+/// A swiss map which might not have any entries.
+///
+/// Swiss maps can't be stored without entries, so the map is wrapped in a
+/// slice which is left empty if there is nothing to store. This happens for
+/// example with kanji components if no KRADFILE is provided.
+#[derive(ZeroCopy)]
+#[repr(C)]
+pub(crate) struct Map<K, V>
+where
+    K: ZeroCopy,
+    V: ZeroCopy,
+{
+    map: Ref<[swiss::MapRef<K, V>]>,
+}
+
+impl<K, V> Map<K, V>
+where
+    K: ZeroCopy,
+    V: ZeroCopy,
+{
+    /// Store a map with the given entries.
+    pub(crate) fn store<I>(buf: &mut OwnedBuf, entries: I) -> Result<Self, Error>
+    where
+        K: Visit,
+        K::Target: Hash,
+        I: IntoIterator<Item = (K, V)>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let entries = entries.into_iter();
+
+        if entries.len() == 0 {
+            return Ok(Self {
+                map: buf.store_slice(&[]),
+            });
+        }
+
+        let map = swiss::store_map(buf, entries)?;
+
+        Ok(Self {
+            map: buf.store_slice(&[map]),
+        })
+    }
+
+    /// Get the value associated with the given key.
+    pub(crate) fn get<'a, Q>(&self, buf: &'a Buf, key: &Q) -> Result<Option<&'a V>, Error>
+    where
+        Q: ?Sized + Visit,
+        Q::Target: Eq + Hash,
+        K: 'a + Visit,
+        K::Target: Borrow<Q::Target>,
+    {
+        match buf.load(self.map)?.first() {
+            Some(map) => map.get(buf, key),
+            None => Ok(None),
+        }
+    }
+
+    /// Test if the map contains the given key.
+    pub(crate) fn contains_key<Q>(&self, buf: &Buf, key: &Q) -> Result<bool, Error>
+    where
+        Q: ?Sized + Visit,
+        Q::Target: Eq + Hash,
+        K: Visit,
+        K::Target: Borrow<Q::Target>,
+    {
+        match buf.load(self.map)?.first() {
+            Some(map) => map.contains_key(buf, key),
+            None => Ok(false),
+        }
+    }
+}
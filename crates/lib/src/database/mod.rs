@@ -1,23 +1,40 @@
 //! Database that can be used as a dictionary.
 
 mod analyze_glossary;
+mod english;
+mod glob;
+mod map;
+mod parallel;
+mod spill;
 
 use std::borrow::Cow;
-use std::cmp::Reverse;
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
-
-use anyhow::{anyhow, Result};
+use std::collections::hash_map::{self, DefaultHasher};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+use std::mem::size_of;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Result};
 use musli::mode::DefaultMode;
 use musli::{Decode, Encode};
 use musli_storage::int::Variable;
 use musli_storage::Encoding;
-use musli_zerocopy::{swiss, Buf, OwnedBuf, Ref, ZeroCopy};
+use musli_zerocopy::{Buf, OwnedBuf, Ref, ZeroCopy};
 use serde::{Deserialize, Serialize};
 
-use crate::inflection::Inflection;
+use crate::accent;
+use crate::furigana::{self, Alignment};
+use crate::inflection::{Explanation, Inflection, Kind};
 use crate::jmdict::{self, EntryKey};
+use crate::jmnedict;
 use crate::kanjidic2;
+use crate::kradfile;
 use crate::romaji::{is_hiragana, is_katakana, Segment};
+use crate::tatoeba;
+
+use self::map::Map;
+use self::spill::Spill;
 use crate::PartOfSpeech;
 use crate::{inflection, romaji};
 
@@ -25,14 +42,82 @@ use crate::{inflection, romaji};
 pub enum Entry<'a> {
     Kanji(kanjidic2::Character<'a>),
     Dict(jmdict::Entry<'a>),
+    Name(jmnedict::Entry<'a>),
+}
+
+/// The maximum number of substrings tracked for reuse when storing keys.
+const MAX_SUBSTRINGS: usize = 1 << 23;
+
+/// Magic bytes identifying a database file.
+const MAGIC: [u8; 8] = *b"JPVDB\0\0\0";
+
+/// The version of the database format. Must be bumped whenever the layout of
+/// the database changes.
+const VERSION: u32 = 8;
+
+/// Size of the little endian CRC32 checksum stored at the end of the database,
+/// which covers everything preceding it including the header.
+const CHECKSUM_SIZE: usize = size_of::<u32>();
+
+/// Fixed header stored at the start of the database.
+#[derive(ZeroCopy)]
+#[repr(C)]
+struct Header {
+    magic: [u8; 8],
+    version: u32,
+    /// Unix timestamp of when the database was built.
+    built: u64,
+    index: Ref<Index>,
+    jmdict_created: Ref<str>,
+    kanjidic2_created: Ref<str>,
+    kanjidic2_version: Ref<str>,
+}
+
+/// Information about a loaded database, see [`Database::info`].
+#[derive(Debug, Clone, Serialize)]
+pub struct Info<'a> {
+    /// The version of the database format.
+    pub version: u32,
+    /// CRC32 checksum of the database.
+    pub checksum: u32,
+    /// Unix timestamp of when the database was built.
+    pub built: u64,
+    /// Creation date of the JMdict source.
+    pub jmdict_created: Option<&'a str>,
+    /// Creation date of the kanjidic2 source.
+    pub kanjidic2_created: Option<&'a str>,
+    /// Database version of the kanjidic2 source.
+    pub kanjidic2_version: Option<&'a str>,
 }
 
 #[derive(ZeroCopy)]
 #[repr(C)]
 pub(super) struct Index {
-    pub(super) lookup: swiss::MapRef<Ref<str>, Ref<[Id]>>,
-    pub(super) by_pos: swiss::MapRef<PartOfSpeech, Ref<[u32]>>,
-    pub(super) by_sequence: swiss::MapRef<u32, u32>,
+    pub(super) lookup: Map<Ref<str>, Ref<[Id]>>,
+    pub(super) by_pos: Map<PartOfSpeech, Ref<[u32]>>,
+    pub(super) by_sequence: Map<u32, u32>,
+    /// Readings and kanji in sorted order, used for prefix matching.
+    pub(super) sorted: Ref<[Ref<str>]>,
+    /// Readings and kanji sorted by their reversed text, used for suffix
+    /// matching.
+    pub(super) reversed: Ref<[Reversed]>,
+    /// Normalized english phrases from glossary entries.
+    pub(super) english: Map<Ref<str>, Ref<[u32]>>,
+    /// Normalized english phrases sorted by character count, used for fuzzy
+    /// matching.
+    pub(super) english_by_len: Ref<[Ref<str>]>,
+    /// Kanji by the components they contain, sorted by stroke count.
+    pub(super) by_component: Map<Ref<str>, Ref<[u32]>>,
+    /// Example sentences by the sequence of the entry they illustrate.
+    pub(super) examples: Map<u32, Ref<[u32]>>,
+}
+
+/// A key stored alongside its reversed text.
+#[derive(ZeroCopy)]
+#[repr(C)]
+pub(super) struct Reversed {
+    pub(super) reversed: Ref<str>,
+    pub(super) key: Ref<str>,
 }
 
 /// Encoding used for storing database.
@@ -111,13 +196,39 @@ pub enum IndexSource {
     VerbInflection {
         reading: inflection::Reading,
         inflection: Inflection,
+        /// The inflection was matched through a colloquial contraction.
+        #[serde(default)]
+        colloquial: bool,
     },
     /// Index was added because of an adjective inflection.
     #[serde(rename = "adj-c")]
     AdjectiveInflection {
         reading: inflection::Reading,
         inflection: Inflection,
+        /// The inflection was matched through a colloquial contraction.
+        #[serde(default)]
+        colloquial: bool,
     },
+    /// Index was added because of a noun conjugated with する.
+    #[serde(rename = "suru-c")]
+    SuruInflection {
+        reading: inflection::Reading,
+        inflection: Inflection,
+        /// The inflection was matched through a colloquial contraction.
+        #[serde(default)]
+        colloquial: bool,
+    },
+    /// Index was added through a normalized english phrase, which might
+    /// differ from the query by the given edit distance.
+    #[serde(rename = "fuzzy")]
+    Fuzzy { distance: u8 },
+    /// Index refers to a proper name from JMnedict.
+    #[serde(rename = "name")]
+    Name,
+    /// Index was added because one of its readings or kanji starts with the
+    /// query, without matching it exactly.
+    #[serde(rename = "prefix")]
+    Prefix,
 }
 
 impl IndexSource {
@@ -126,9 +237,33 @@ impl IndexSource {
         match self {
             IndexSource::VerbInflection { .. } => true,
             IndexSource::AdjectiveInflection { .. } => true,
+            IndexSource::SuruInflection { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Test if the index was added through a colloquial contraction, like
+    /// 食べてる.
+    pub fn is_colloquial(&self) -> bool {
+        match *self {
+            IndexSource::VerbInflection { colloquial, .. } => colloquial,
+            IndexSource::AdjectiveInflection { colloquial, .. } => colloquial,
+            IndexSource::SuruInflection { colloquial, .. } => colloquial,
             _ => false,
         }
     }
+
+    /// Explain the inflection this index was added through, if any.
+    pub fn explain(&self) -> Option<Explanation> {
+        let (inflection, kind) = match *self {
+            IndexSource::VerbInflection { inflection, .. } => (inflection, Kind::Verb),
+            IndexSource::AdjectiveInflection { inflection, .. } => (inflection, Kind::Adjective),
+            IndexSource::SuruInflection { inflection, .. } => (inflection, Kind::Noun),
+            _ => return None,
+        };
+
+        Some(inflection::explain(inflection, kind))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, ZeroCopy)]
@@ -146,6 +281,20 @@ impl Id {
         }
     }
 
+    fn fuzzy(index: u32, distance: u8) -> Self {
+        Self {
+            index,
+            source: IndexSource::Fuzzy { distance },
+        }
+    }
+
+    fn name(index: u32) -> Self {
+        Self {
+            index,
+            source: IndexSource::Name,
+        }
+    }
+
     fn kanji_reading(index: u32, reading: KanjiReading) -> Self {
         Self {
             index,
@@ -153,12 +302,18 @@ impl Id {
         }
     }
 
-    fn verb_inflection(index: u32, reading: inflection::Reading, inflection: Inflection) -> Self {
+    fn verb_inflection(
+        index: u32,
+        reading: inflection::Reading,
+        inflection: Inflection,
+        colloquial: bool,
+    ) -> Self {
         Self {
             index,
             source: IndexSource::VerbInflection {
                 reading,
                 inflection,
+                colloquial,
             },
         }
     }
@@ -167,12 +322,30 @@ impl Id {
         index: u32,
         reading: inflection::Reading,
         inflection: Inflection,
+        colloquial: bool,
     ) -> Self {
         Self {
             index,
             source: IndexSource::AdjectiveInflection {
                 reading,
                 inflection,
+                colloquial,
+            },
+        }
+    }
+
+    fn suru_inflection(
+        index: u32,
+        reading: inflection::Reading,
+        inflection: Inflection,
+        colloquial: bool,
+    ) -> Self {
+        Self {
+            index,
+            source: IndexSource::SuruInflection {
+                reading,
+                inflection,
+                colloquial,
             },
         }
     }
@@ -188,191 +361,447 @@ impl Id {
     }
 }
 
+/// The maximum number of characters considered for a single token when
+/// segmenting.
+const MAX_TOKEN: usize = 24;
+
+/// The cost of a character which could not be matched to any entry when
+/// segmenting.
+const UNKNOWN_COST: f32 = 10.0;
+
+/// Lookups and decoded entries which are shared between the overlapping spans
+/// considered by [`Database::segment`], since many of them deinflect to the
+/// same dictionary forms.
+#[derive(Default)]
+struct Cache<'a> {
+    lookups: HashMap<String, Vec<Id>>,
+    entries: HashMap<u32, Option<jmdict::Entry<'a>>>,
+}
+
+impl<'a> Cache<'a> {
+    fn lookup(&mut self, db: &Database<'a>, query: &str) -> Result<&[Id]> {
+        let ids = match self.lookups.entry(query.to_owned()) {
+            hash_map::Entry::Occupied(e) => e.into_mut(),
+            hash_map::Entry::Vacant(e) => e.insert(db.lookup(query)?),
+        };
+
+        Ok(ids)
+    }
+
+    /// Get the dictionary entry with the given identifier, or `None` if it
+    /// refers to something else, like a kanji.
+    fn entry(&mut self, db: &Database<'a>, id: Id) -> Result<Option<&jmdict::Entry<'a>>> {
+        let entry = match self.entries.entry(id.index()) {
+            hash_map::Entry::Occupied(e) => e.into_mut(),
+            hash_map::Entry::Vacant(e) => e.insert(match db.get(id)? {
+                Entry::Dict(entry) => Some(entry),
+                _ => None,
+            }),
+        };
+
+        Ok(entry.as_ref())
+    }
+}
+
+/// A single token produced by [`Database::segment`].
+pub struct Token<'a> {
+    /// Byte offset where the token starts in the input.
+    pub start: usize,
+    /// Byte offset where the token ends in the input.
+    pub end: usize,
+    /// The entry the token was matched against, if any.
+    pub entry: Option<(EntryResultKey, jmdict::Entry<'a>)>,
+}
+
 /// A search result.
 pub struct Search<'a> {
     pub entries: Vec<(EntryResultKey, jmdict::Entry<'a>)>,
     pub characters: Vec<kanjidic2::Character<'a>>,
+    pub names: Vec<jmnedict::Entry<'a>>,
+}
+
+/// Example sentences for an entry, see [`Database::examples_for`].
+pub struct Examples<'a> {
+    data: &'a Buf,
+    refs: &'a [u32],
+}
+
+impl<'a> Examples<'a> {
+    /// The total number of examples.
+    pub fn len(&self) -> usize {
+        self.refs.len()
+    }
+
+    /// Test if there are no examples.
+    pub fn is_empty(&self) -> bool {
+        self.refs.is_empty()
+    }
+
+    /// Load at most `limit` examples, starting at `offset`.
+    pub fn page(&self, offset: usize, limit: usize) -> Result<Vec<tatoeba::Sentence<'a>>> {
+        let mut output = Vec::new();
+
+        for &sentence_ref in self.refs.iter().skip(offset).take(limit) {
+            let Some(bytes) = self.data.get(sentence_ref as usize..) else {
+                return Err(anyhow!("Missing example at {sentence_ref}"));
+            };
+
+            output.push(ENCODING.from_slice(bytes)?);
+        }
+
+        Ok(output)
+    }
+}
+
+/// Sources used to build the database.
+#[derive(Default)]
+pub struct Input<'a> {
+    /// JMdict, which is read incrementally.
+    pub jmdict: Option<Box<dyn Read + 'a>>,
+    /// Kanjidic2, which is read incrementally.
+    pub kanjidic2: Option<Box<dyn Read + 'a>>,
+    /// Contents of any number of KRADFILE or RADKFILE, converted to UTF-8.
+    pub kradfile: Vec<&'a str>,
+    /// Tab-separated pitch accent data, see [`accent::parse`].
+    pub accent: Option<&'a str>,
+    /// JMnedict, which is read incrementally.
+    pub jmnedict: Option<Box<dyn Read + 'a>>,
+    /// Tatoeba example sentences, see [`tatoeba::parse`].
+    pub tatoeba: Option<&'a str>,
+    /// Unix timestamp to record as the time the database was built, which
+    /// defaults to the current time.
+    pub built: Option<u64>,
+    /// The number of index keys kept in memory before they are spilled to
+    /// disk, which defaults to a threshold suitable for the full
+    /// dictionaries.
+    pub spill_threshold: Option<usize>,
 }
 
 /// Load the given dictionary and convert into the internal format.
-pub fn load(jmdict: &str, kanjidic2: &str) -> Result<OwnedBuf> {
+pub fn load(input: Input<'_>) -> Result<OwnedBuf> {
     let mut buf = OwnedBuf::new();
 
+    let header = buf.store_uninit::<Header>();
     let index = buf.store_uninit::<Index>();
     let mut output = Vec::new();
 
-    let mut kanjidic2 = kanjidic2::Parser::new(kanjidic2);
-    let mut readings = Vec::new();
+    let mut spill = match input.spill_threshold {
+        Some(threshold) => Spill::with_threshold(threshold),
+        None => Spill::new(),
+    };
 
-    tracing::info!("Parsing kanjidic");
+    let mut literals = HashMap::new();
+    let mut kanji_readings = furigana::Readings::new();
+    let mut kanjidic2_header = None;
 
-    while let Some(c) = kanjidic2.parse()? {
-        output.clear();
-        ENCODING.to_writer(&mut output, &c)?;
+    if let Some(input) = input.kanjidic2 {
+        tracing::info!("Parsing kanjidic");
 
-        let kanji_ref = buf.store_slice(&output).offset() as u32;
+        let mut reader = kanjidic2::Reader::new(input);
 
-        readings.push((
-            Cow::Borrowed(c.literal),
-            Id::kanji_reading(kanji_ref, KanjiReading::Literal),
-        ));
+        while let Some(chunk) = reader.next_chunk()? {
+            let mut kanjidic2 = chunk.parser();
+            let mut readings = Vec::new();
 
-        for reading in &c.reading_meaning.readings {
-            match reading.ty {
-                "ja_kun" => {
-                    if let Some((prefix, _)) = reading.text.split_once('.') {
-                        let a = Id::kanji_reading(kanji_ref, KanjiReading::KunyomiRomanize);
-                        let b = Id::kanji_reading(kanji_ref, KanjiReading::KunyomiKatakana);
-                        other_readings(&mut readings, prefix, a, b, |s| s.katakana());
-                        let id = Id::kanji_reading(kanji_ref, KanjiReading::Kunyomi);
-                        readings.push((Cow::Borrowed(prefix), id));
-                    }
+            while let Some(c) = kanjidic2.parse()? {
+                output.clear();
+                ENCODING.to_writer(&mut output, &c)?;
 
-                    let a = Id::kanji_reading(kanji_ref, KanjiReading::KunyomiFullRomanize);
-                    let b = Id::kanji_reading(kanji_ref, KanjiReading::KunyomiFullKatakana);
-                    other_readings(&mut readings, reading.text, a, b, |s| s.katakana());
+                let kanji_ref = buf.store_slice(&output).offset() as u32;
 
-                    let id = Id::kanji_reading(kanji_ref, KanjiReading::KunyomiFull);
-                    readings.push((Cow::Borrowed(reading.text), id));
-                }
-                "ja_on" => {
-                    let a = Id::kanji_reading(kanji_ref, KanjiReading::OnyomiRomanize);
-                    let b = Id::kanji_reading(kanji_ref, KanjiReading::OnyomiHiragana);
-                    other_readings(&mut readings, reading.text, a, b, |s| s.hiragana());
-                    let id = Id::kanji_reading(kanji_ref, KanjiReading::Onyomi);
-                    readings.push((Cow::Borrowed(reading.text), id));
+                literals.insert(c.literal.to_owned(), (c.misc.stroke_count(), kanji_ref));
+                kanji_readings.insert(&c);
+
+                readings.push((
+                    Cow::Borrowed(c.literal),
+                    Id::kanji_reading(kanji_ref, KanjiReading::Literal),
+                ));
+
+                for reading in &c.reading_meaning.readings {
+                    match reading.ty {
+                        "ja_kun" => {
+                            if let Some((prefix, _)) = reading.text.split_once('.') {
+                                let a = Id::kanji_reading(kanji_ref, KanjiReading::KunyomiRomanize);
+                                let b = Id::kanji_reading(kanji_ref, KanjiReading::KunyomiKatakana);
+                                other_readings(&mut readings, prefix, a, b, |s| s.katakana());
+                                let id = Id::kanji_reading(kanji_ref, KanjiReading::Kunyomi);
+                                readings.push((Cow::Borrowed(prefix), id));
+                            }
+
+                            let a = Id::kanji_reading(kanji_ref, KanjiReading::KunyomiFullRomanize);
+                            let b = Id::kanji_reading(kanji_ref, KanjiReading::KunyomiFullKatakana);
+                            other_readings(&mut readings, reading.text, a, b, |s| s.katakana());
+
+                            let id = Id::kanji_reading(kanji_ref, KanjiReading::KunyomiFull);
+                            readings.push((Cow::Borrowed(reading.text), id));
+                        }
+                        "ja_on" => {
+                            let a = Id::kanji_reading(kanji_ref, KanjiReading::OnyomiRomanize);
+                            let b = Id::kanji_reading(kanji_ref, KanjiReading::OnyomiHiragana);
+                            other_readings(&mut readings, reading.text, a, b, |s| s.hiragana());
+                            let id = Id::kanji_reading(kanji_ref, KanjiReading::Onyomi);
+                            readings.push((Cow::Borrowed(reading.text), id));
+                        }
+                        _ => {
+                            let id = Id::kanji_reading(kanji_ref, KanjiReading::Other);
+                            readings.push((Cow::Borrowed(reading.text), id));
+                        }
+                    };
                 }
-                _ => {
-                    let id = Id::kanji_reading(kanji_ref, KanjiReading::Other);
-                    readings.push((Cow::Borrowed(reading.text), id));
+
+                for meaning in &c.reading_meaning.meanings {
+                    let id = Id::kanji_reading(kanji_ref, KanjiReading::Meaning);
+                    populate_analyzed(meaning.text, &mut readings, id);
                 }
-            };
-        }
+            }
 
-        for meaning in &c.reading_meaning.meanings {
-            let id = Id::kanji_reading(kanji_ref, KanjiReading::Meaning);
-            populate_analyzed(meaning.text, &mut readings, id);
+            if let Some(header) = kanjidic2.header() {
+                kanjidic2_header = Some(borrowme::to_owned(header));
+            }
+
+            spill.extend(readings)?;
         }
     }
 
-    tracing::info!("Parsing JMdict");
+    let mut by_component = BTreeMap::<_, BTreeSet<_>>::new();
 
-    let mut jmdict = jmdict::Parser::new(jmdict);
+    for kradfile in &input.kradfile {
+        tracing::info!("Parsing kradfile");
 
-    let mut by_sequence = HashMap::new();
-    let mut by_pos = HashMap::<_, HashSet<_>>::new();
+        kradfile::parse(kradfile, |kanji, component| {
+            if let Some(&(strokes, kanji_ref)) = literals.get(kanji) {
+                by_component.entry(component).or_default().insert((
+                    strokes.unwrap_or(u8::MAX),
+                    kanji,
+                    kanji_ref,
+                ));
+            }
+        })?;
+    }
 
-    while let Some(entry) = jmdict.parse()? {
-        output.clear();
-        ENCODING.to_writer(&mut output, &entry)?;
+    let mut accents = HashMap::new();
 
-        let entry_ref = buf.store_slice(&output).offset() as u32;
+    if let Some(accent) = input.accent {
+        tracing::info!("Parsing accents");
 
-        by_sequence.insert(entry.sequence as u32, entry_ref);
+        accent::parse(accent, |kanji, reading, accent| {
+            accents.entry((kanji, reading)).or_insert(accent);
+        })?;
+    }
 
-        for sense in &entry.senses {
-            for pos in &sense.pos {
-                by_pos.entry(pos).or_default().insert(entry_ref);
-            }
+    let mut by_sequence = BTreeMap::new();
+    let mut by_pos = BTreeMap::<_, BTreeSet<_>>::new();
+    let mut words = HashSet::new();
+    let mut english = BTreeMap::<_, BTreeSet<_>>::new();
+    // Headwords and readings are only needed to link example sentences to
+    // entries.
+    let link_examples = input.tatoeba.is_some();
+    let mut headwords = HashMap::<_, Vec<_>>::new();
+    let mut entry_readings = HashSet::new();
 
-            let id = Id::new(entry_ref);
+    let mut jmdict_created = None;
 
-            for g in &sense.gloss {
-                if g.ty == Some("expl") {
-                    continue;
-                }
+    if let Some(input) = input.jmdict {
+        tracing::info!("Parsing JMdict");
 
-                populate_analyzed(g.text, &mut readings, id);
-            }
-        }
+        let mut reader = jmdict::Reader::new(input);
 
-        for el in &entry.reading_elements {
-            readings.push((Cow::Borrowed(el.text), Id::new(entry_ref)));
-        }
+        parallel::process(
+            || reader.next_chunk(),
+            |chunk| prepare_jmdict(chunk, &accents, &kanji_readings),
+            |chunk| {
+                let mut readings = Vec::new();
 
-        for el in &entry.kanji_elements {
-            readings.push((Cow::Borrowed(el.text), Id::new(entry_ref)));
-        }
+                for entry in chunk.entries {
+                    let entry_ref = buf.store_slice(&entry.data).offset() as u32;
+
+                    by_sequence.insert(entry.sequence, entry_ref);
+
+                    for pos in entry.pos {
+                        by_pos.entry(pos).or_default().insert(entry_ref);
+                    }
 
-        for (reading, c, kind) in inflection::conjugate(&entry) {
-            for (inflection, pair) in c.iter() {
-                for word in [pair.text(), pair.reading()] {
-                    let key = Cow::Owned(format!("{}{}", word, pair.suffix()));
+                    for phrase in entry.english {
+                        english.entry(phrase).or_default().insert(entry_ref);
+                    }
+
+                    for text in entry.reading_elements {
+                        words.insert(text.clone());
 
-                    let id = match kind {
-                        inflection::Kind::Verb => {
-                            Id::verb_inflection(entry_ref, reading, *inflection)
+                        if link_examples {
+                            entry_readings.insert((entry.sequence, text.clone()));
+                            headwords.entry(text).or_default().push(entry.sequence);
                         }
-                        inflection::Kind::Adjective => {
-                            Id::adjective_inflection(entry_ref, reading, *inflection)
+                    }
+
+                    for text in entry.kanji_elements {
+                        words.insert(text.clone());
+
+                        if link_examples {
+                            headwords.entry(text).or_default().push(entry.sequence);
                         }
-                    };
+                    }
+
+                    readings.extend(entry.keys.into_iter().map(|(key, mut id)| {
+                        id.index = entry_ref;
+                        (Cow::Owned(key), id)
+                    }));
+                }
+
+                if let Some(created) = chunk.created {
+                    jmdict_created = Some(created);
+                }
+
+                spill.extend(readings)
+            },
+        )?;
+    }
+
+    let mut examples = BTreeMap::<_, Vec<_>>::new();
+
+    if let Some(input) = input.tatoeba {
+        tracing::info!("Parsing tatoeba");
+
+        let mut sentences = Vec::new();
+
+        tatoeba::parse(input, |sentence| {
+            sentences.push(sentence);
+        })?;
+
+        for sentence in sentences {
+            output.clear();
+            ENCODING.to_writer(&mut output, &sentence)?;
+            let sentence_ref = buf.store_slice(&output).offset() as u32;
+
+            let mut linked = HashMap::<_, bool>::new();
+
+            for word in &sentence.words {
+                let Some(sequences) = headwords.get(word.headword) else {
+                    continue;
+                };
+
+                // Use the reading to disambiguate between entries sharing
+                // the same headword.
+                let matching = sequences
+                    .iter()
+                    .filter(|&&s| match word.reading {
+                        Some(r) => entry_readings.contains(&(s, r.to_owned())),
+                        None => true,
+                    })
+                    .collect::<Vec<_>>();
+
+                let sequences = if matching.is_empty() {
+                    sequences.iter().collect()
+                } else {
+                    matching
+                };
 
-                    readings.push((key, id));
+                for &sequence in sequences {
+                    *linked.entry(sequence).or_default() |= word.checked;
                 }
             }
+
+            for (sequence, checked) in linked {
+                examples
+                    .entry(sequence)
+                    .or_default()
+                    .push((!checked, sentence_ref));
+            }
         }
     }
 
-    tracing::info!("Sorting readings");
-    readings.sort_by_cached_key(|a| (Reverse(a.0.chars().count()), a.0.clone()));
+    if let Some(input) = input.jmnedict {
+        tracing::info!("Parsing JMnedict");
+
+        let mut reader = jmnedict::Reader::new(input);
+
+        parallel::process(
+            || reader.next_chunk(),
+            prepare_jmnedict,
+            |entries| {
+                let mut readings = Vec::new();
+
+                for entry in entries {
+                    let index = buf.store_slice(&entry.data).offset() as u32;
+
+                    readings.extend(entry.keys.into_iter().map(|(key, mut id)| {
+                        id.index = index;
+                        (Cow::Owned(key), id)
+                    }));
+                }
+
+                spill.extend(readings)
+            },
+        )?;
+    }
 
-    let mut lookup = HashMap::<_, Vec<_>>::new();
-    let mut queue = VecDeque::new();
+    tracing::info!("Inserting {} readings", spill.len());
 
-    tracing::info!("Inserting {} readings", readings.len());
+    let mut merge = spill.finish()?;
+    let mut lookup = Vec::new();
+    let mut sorted = Vec::new();
 
     {
-        let mut existing = HashMap::<_, usize>::new();
+        let mut existing = HashMap::<u64, usize>::new();
+        let mut current = None::<(String, Ref<str>, Vec<Id>)>;
         let mut reuse = 0usize;
         let mut total = 0usize;
 
-        for (index, (key, id)) in readings.iter().enumerate() {
-            if index % 100000 == 0 {
-                tracing::info!("Building strings: {}: {key}", index);
+        while let Some((key, id)) = merge.next()? {
+            if let Some((current, _, ids)) = &mut current {
+                if *current == key {
+                    ids.push(id);
+                    continue;
+                }
             }
 
-            total += 1;
-
-            let (unsize, substring) = if let Some(existing) = existing.get(key.as_ref()) {
-                reuse += 1;
-                let unsize = Ref::with_metadata(*existing, key.len());
-                debug_assert_eq!(buf.load(unsize)?, key.as_ref());
-                (unsize, true)
-            } else {
-                let unsize = buf.store_unsized(key.as_ref());
-                (unsize, false)
-            };
+            if let Some((_, unsize, ids)) = current.take() {
+                lookup.push((unsize, buf.store_slice(&ids)));
+            }
 
-            lookup.entry(unsize).or_default().push(*id);
+            if total % 100000 == 0 {
+                tracing::info!("Building strings: {}: {key}", total);
+            }
 
-            if !substring && !existing.contains_key(key.as_ref()) {
-                let mut it = key.char_indices();
+            total += 1;
 
-                queue.push_back(it.clone());
+            let reused = existing
+                .get(&hash(&key))
+                .map(|&offset| Ref::<str>::with_metadata(offset, key.len()))
+                .filter(|unsize| buf.load(*unsize).ok() == Some(key.as_str()));
 
-                while it.next_back().is_some() && !it.as_str().is_empty() {
-                    if !existing.contains_key(it.as_str()) {
-                        queue.push_back(it.clone());
+            let unsize = if let Some(unsize) = reused {
+                reuse += 1;
+                unsize
+            } else {
+                let unsize = buf.store_unsized(key.as_str());
+
+                // Register substrings of the stored key so that shorter keys
+                // can reuse it, bounded to keep memory use in check.
+                if existing.len() < MAX_SUBSTRINGS {
+                    for (start, _) in key.char_indices() {
+                        for (end, c) in key[start..].char_indices() {
+                            let end = start + end + c.len_utf8();
+
+                            existing
+                                .entry(hash(&key[start..end]))
+                                .or_insert(unsize.offset() + start);
+                        }
                     }
                 }
 
-                while let Some(mut it) = queue.pop_front() {
-                    if !existing.contains_key(it.as_str()) {
-                        existing.insert(it.as_str(), unsize.offset());
-                    }
-
-                    while let Some((base, c)) = it.next() {
-                        if it.as_str().is_empty() {
-                            continue;
-                        }
+                unsize
+            };
 
-                        if !existing.contains_key(it.as_str()) {
-                            existing.insert(it.as_str(), unsize.offset() + base + c.len_utf8());
-                        }
-                    }
-                }
+            if words.remove(key.as_str()) {
+                sorted.push((key.clone(), unsize));
             }
+
+            current = Some((key, unsize, vec![id]));
+        }
+
+        if let Some((_, unsize, ids)) = current.take() {
+            lookup.push((unsize, buf.store_slice(&ids)));
         }
 
         tracing::info!("Reused {} string(s) (out of {})", reuse, total);
@@ -381,20 +810,8 @@ pub fn load(jmdict: &str, kanjidic2: &str) -> Result<OwnedBuf> {
     tracing::info!("Serializing to zerocopy structure (at {})", buf.len());
 
     let lookup = {
-        let mut entries = Vec::new();
-
-        for (index, (key, set)) in lookup.into_iter().enumerate() {
-            if index % 100000 == 0 {
-                tracing::info!("Building lookup: {}", index);
-            }
-
-            let slice = buf.store_slice(&set);
-
-            entries.push((key, slice));
-        }
-
-        tracing::info!("Storing lookup {}:...", entries.len());
-        swiss::store_map(&mut buf, entries)?
+        tracing::info!("Storing lookup {}:...", lookup.len());
+        Map::store(&mut buf, lookup)?
     };
 
     let by_pos = {
@@ -405,105 +822,519 @@ pub fn load(jmdict: &str, kanjidic2: &str) -> Result<OwnedBuf> {
                 tracing::info!("{}", index);
             }
 
-            let mut values = Vec::new();
-
-            for v in set {
-                values.push(v);
-            }
-
-            values.sort();
+            let values = set.into_iter().collect::<Vec<_>>();
             let set = buf.store_slice(&values);
             entries.push((key, set));
         }
 
         tracing::info!("Storing by_pos: {}...", entries.len());
-        swiss::store_map(&mut buf, entries)?
+        Map::store(&mut buf, entries)?
     };
 
     let by_sequence = {
         tracing::info!("Storing by_sequence: {}...", by_sequence.len());
-        swiss::store_map(&mut buf, by_sequence)?
+        Map::store(&mut buf, by_sequence)?
     };
 
-    buf.load_uninit_mut(index).write(&Index {
-        lookup,
-        by_pos,
-        by_sequence,
-    });
+    let reversed = {
+        tracing::info!("Storing reversed: {}...", sorted.len());
 
-    Ok(buf)
-}
+        let mut entries = Vec::with_capacity(sorted.len());
 
-fn populate_analyzed<'a>(text: &'a str, readings: &mut Vec<(Cow<'a, str>, Id)>, id: Id) {
-    fn is_common(phrase: &str) -> bool {
-        match phrase {
-            "to" | "a" | "in" | "of" | "for" | "so" | "if" | "by" | "but" | "not" | "any"
-            | "way" | "into" => true,
-            string if string.starts_with("e.g.") => {
-                if let Some(rest) = string.strip_prefix("e.g. ") {
-                    is_common(rest)
-                } else {
-                    string == "e.g."
-                }
-            }
-            _ => false,
+        for (key, unsize) in &sorted {
+            let reversed = key.chars().rev().collect::<String>();
+            entries.push((reversed, *unsize));
         }
-    }
 
-    for phrase in analyze_glossary::analyze(text) {
-        if phrase.chars().count() > 32 {
-            continue;
-        }
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
 
-        // Skip overly common prefixes which would mostly just be
-        // unhelpful to index.
-        if !is_common(phrase) {
-            readings.push((Cow::Borrowed(phrase), id));
+        let mut values = Vec::with_capacity(entries.len());
+
+        for (reversed, key) in entries {
+            let reversed = buf.store_unsized(reversed.as_str());
+            values.push(Reversed { reversed, key });
         }
-    }
-}
 
-fn other_readings(
-    output: &mut Vec<(Cow<'_, str>, Id)>,
-    text: &str,
-    a: Id,
-    b: Id,
-    f: for<'a> fn(&'a Segment<'_>) -> &'a str,
-) {
-    let mut romanized = String::new();
-    let mut other = String::new();
+        buf.store_slice(&values)
+    };
 
-    for part in romaji::analyze(text) {
-        romanized += part.romanize();
-        other += f(&part);
-    }
+    let (english, english_by_len) = {
+        tracing::info!("Storing english: {}...", english.len());
 
-    output.push((Cow::Owned(romanized), a));
-    output.push((Cow::Owned(other), b));
-}
+        let mut entries = Vec::with_capacity(english.len());
+        let mut by_len = Vec::with_capacity(english.len());
 
-#[derive(Clone)]
-pub struct Database<'a> {
-    index: &'a Index,
-    data: &'a Buf,
-}
+        for (phrase, set) in english {
+            let key = buf.store_unsized(phrase.as_str());
+            let values = set.into_iter().collect::<Vec<_>>();
+            entries.push((key, buf.store_slice(&values)));
+            by_len.push((phrase, key));
+        }
 
-impl<'a> Database<'a> {
-    /// Construct a new database wrapper.
-    pub fn new(data: &'a [u8]) -> Result<Self> {
-        let data = Buf::new(data);
-        let index = data.load(Ref::<Index>::zero())?;
+        by_len.sort_by_cached_key(|(phrase, _)| (phrase.chars().count(), phrase.clone()));
+        let by_len = by_len.into_iter().map(|(_, key)| key).collect::<Vec<_>>();
 
-        Ok(Self { index, data })
-    }
+        (Map::store(&mut buf, entries)?, buf.store_slice(&by_len))
+    };
 
-    /// Get identifier by sequence.
-    pub fn lookup_sequence(&self, sequence: u32) -> Result<Option<Id>> {
-        let Some(index) = self.index.by_sequence.get(self.data, &sequence)? else {
-            return Ok(None);
-        };
+    let by_component = {
+        tracing::info!("Storing by_component: {}...", by_component.len());
 
-        Ok(Some(Id::new(*index)))
+        let mut entries = Vec::with_capacity(by_component.len());
+
+        for (component, set) in by_component {
+            let key = buf.store_unsized(component);
+            let values = set.into_iter().map(|(_, _, r)| r).collect::<Vec<_>>();
+            entries.push((key, buf.store_slice(&values)));
+        }
+
+        Map::store(&mut buf, entries)?
+    };
+
+    let examples = {
+        tracing::info!("Storing examples: {}...", examples.len());
+
+        let mut entries = Vec::with_capacity(examples.len());
+
+        for (sequence, mut values) in examples {
+            // Checked examples first, otherwise in the order of the corpus.
+            values.sort_by_key(|&(unchecked, _)| unchecked);
+            let values = values.into_iter().map(|(_, r)| r).collect::<Vec<_>>();
+            entries.push((sequence, buf.store_slice(&values)));
+        }
+
+        Map::store(&mut buf, entries)?
+    };
+
+    let sorted = {
+        tracing::info!("Storing sorted: {}...", sorted.len());
+        sorted.sort_by(|a, b| a.0.cmp(&b.0));
+        let values = sorted.into_iter().map(|(_, key)| key).collect::<Vec<_>>();
+        buf.store_slice(&values)
+    };
+
+    buf.load_uninit_mut(index).write(&Index {
+        lookup,
+        by_pos,
+        by_sequence,
+        sorted,
+        reversed,
+        english,
+        english_by_len,
+        by_component,
+        examples,
+    });
+
+    let jmdict_created = buf.store_unsized(jmdict_created.as_deref().unwrap_or_default());
+
+    let (kanjidic2_created, kanjidic2_version) = match &kanjidic2_header {
+        Some(header) => (
+            header.date_of_creation.as_str(),
+            header.database_version.as_str(),
+        ),
+        None => ("", ""),
+    };
+
+    let kanjidic2_created = buf.store_unsized(kanjidic2_created);
+    let kanjidic2_version = buf.store_unsized(kanjidic2_version);
+
+    let built = input.built.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+    });
+
+    buf.load_uninit_mut(header).write(&Header {
+        magic: MAGIC,
+        version: VERSION,
+        built,
+        index: index.assume_init(),
+        jmdict_created,
+        kanjidic2_created,
+        kanjidic2_version,
+    });
+
+    // The checksum is stored last so that it covers the header as well, and
+    // in little endian so that it doesn't depend on the host.
+    let trailer = buf.store_uninit::<[u8; CHECKSUM_SIZE]>();
+    let checksum = crc32fast::hash(&buf.as_slice()[..trailer.offset()]);
+    buf.load_uninit_mut(trailer).write(&checksum.to_le_bytes());
+
+    Ok(buf)
+}
+
+/// A JMdict entry which has been prepared for insertion into the database.
+///
+/// Index keys are constructed with a placeholder index, which is replaced with
+/// the offset of the entry once it's been stored.
+struct PreparedEntry {
+    sequence: u32,
+    data: Vec<u8>,
+    pos: Vec<PartOfSpeech>,
+    english: Vec<String>,
+    reading_elements: Vec<String>,
+    kanji_elements: Vec<String>,
+    keys: Vec<(String, Id)>,
+}
+
+/// A chunk of JMdict which has been prepared for insertion into the database.
+struct PreparedJmdict {
+    entries: Vec<PreparedEntry>,
+    created: Option<String>,
+}
+
+/// Parse a chunk of JMdict, encoding its entries and generating their index
+/// keys.
+fn prepare_jmdict(
+    chunk: &jmdict::Chunk,
+    accents: &HashMap<(&str, &str), Vec<u8>>,
+    kanji_readings: &furigana::Readings,
+) -> Result<PreparedJmdict> {
+    let mut parser = chunk.parser();
+    let mut entries = Vec::new();
+
+    while let Some(mut entry) = parser.parse()? {
+        if !accents.is_empty() {
+            populate_accents(&mut entry, accents);
+        }
+
+        if !kanji_readings.is_empty() {
+            populate_furigana(&mut entry, kanji_readings);
+        }
+
+        let mut data = Vec::new();
+        ENCODING.to_writer(&mut data, &entry)?;
+
+        let mut pos = Vec::new();
+        let mut english = Vec::new();
+        let mut keys = Vec::new();
+
+        let id = Id::new(0);
+
+        for sense in &entry.senses {
+            pos.extend(sense.pos.iter());
+
+            for g in &sense.gloss {
+                if g.ty == Some("expl") {
+                    continue;
+                }
+
+                populate_analyzed(g.text, &mut keys, id);
+
+                if matches!(g.lang, None | Some("eng")) {
+                    for phrase in analyze_glossary::analyze(g.text) {
+                        let phrase = english::normalize(phrase);
+
+                        if !phrase.is_empty() && phrase.chars().count() <= 32 {
+                            english.push(phrase);
+                        }
+                    }
+                }
+            }
+        }
+
+        for el in &entry.reading_elements {
+            keys.push((Cow::Borrowed(el.text), id));
+        }
+
+        for el in &entry.kanji_elements {
+            keys.push((Cow::Borrowed(el.text), id));
+        }
+
+        entries.push(PreparedEntry {
+            sequence: entry.sequence as u32,
+            data,
+            pos,
+            english,
+            reading_elements: entry
+                .reading_elements
+                .iter()
+                .map(|el| el.text.to_owned())
+                .collect(),
+            kanji_elements: entry
+                .kanji_elements
+                .iter()
+                .map(|el| el.text.to_owned())
+                .collect(),
+            keys: keys
+                .into_iter()
+                .map(|(key, id)| (key.into_owned(), id))
+                .collect(),
+        });
+    }
+
+    Ok(PreparedJmdict {
+        entries,
+        created: parser.created().map(str::to_owned),
+    })
+}
+
+/// A JMnedict entry which has been prepared for insertion into the database.
+struct PreparedName {
+    data: Vec<u8>,
+    keys: Vec<(String, Id)>,
+}
+
+/// Parse a chunk of JMnedict, encoding its entries and generating their index
+/// keys.
+fn prepare_jmnedict(chunk: &jmnedict::Chunk) -> Result<Vec<PreparedName>> {
+    let mut parser = chunk.parser();
+    let mut entries = Vec::new();
+
+    while let Some(entry) = parser.parse()? {
+        let mut data = Vec::new();
+        ENCODING.to_writer(&mut data, &entry)?;
+
+        let id = Id::name(0);
+        let mut keys = Vec::new();
+
+        for el in &entry.reading_elements {
+            keys.push((Cow::Borrowed(el.text), id));
+        }
+
+        for el in &entry.kanji_elements {
+            keys.push((Cow::Borrowed(el.text), id));
+        }
+
+        for t in &entry.translations {
+            for text in &t.text {
+                populate_analyzed(text, &mut keys, id);
+            }
+        }
+
+        entries.push(PreparedName {
+            data,
+            keys: keys
+                .into_iter()
+                .map(|(key, id)| (key.into_owned(), id))
+                .collect(),
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Hash a key for the purpose of finding substrings to reuse.
+fn hash(key: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The edit distance an entry was matched with, which is zero unless it was
+/// only found through fuzzy matching.
+fn distance(sources: &BTreeSet<IndexSource>) -> u8 {
+    let mut distance = None::<u8>;
+
+    for source in sources {
+        match *source {
+            IndexSource::Fuzzy { distance: d } => {
+                distance = Some(distance.map_or(d, |c| c.min(d)));
+            }
+            _ => return 0,
+        }
+    }
+
+    distance.unwrap_or_default()
+}
+
+/// Test if the only way an entry was found was by a prefix of the input.
+fn prefix_only(sources: &BTreeSet<IndexSource>) -> bool {
+    sources.iter().all(|source| *source == IndexSource::Prefix)
+}
+
+/// Binary search for the first element in a sorted slice for which the
+/// predicate is false.
+fn partition_point<T>(slice: &[T], mut pred: impl FnMut(&T) -> Result<bool>) -> Result<usize> {
+    let mut lo = 0;
+    let mut hi = slice.len();
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+
+        if pred(&slice[mid])? {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+
+    Ok(lo)
+}
+
+/// Populate accents for all readings in the entry, preferring the accent of
+/// the first kanji the reading applies to.
+fn populate_accents(entry: &mut jmdict::Entry<'_>, accents: &HashMap<(&str, &str), Vec<u8>>) {
+    for reading in &mut entry.reading_elements {
+        let kanji = entry
+            .kanji_elements
+            .iter()
+            .filter(|kanji| reading.applies_to(kanji.text))
+            .map(|kanji| kanji.text);
+
+        for kanji in kanji.chain([reading.text]) {
+            if let Some(accent) = accents.get(&(kanji, reading.text)) {
+                reading.accent = accent.clone();
+                break;
+            }
+        }
+    }
+}
+
+/// Populate per-kanji furigana alignments for all kanji elements each reading
+/// applies to.
+fn populate_furigana(entry: &mut jmdict::Entry<'_>, kanji_readings: &furigana::Readings) {
+    for reading in &mut entry.reading_elements {
+        for kanji in &entry.kanji_elements {
+            if !reading.applies_to(kanji.text) {
+                continue;
+            }
+
+            if let Some(lengths) = furigana::align(kanji.text, reading.text, kanji_readings) {
+                reading.furigana.push(Alignment {
+                    kanji: kanji.text,
+                    lengths,
+                });
+            }
+        }
+    }
+}
+
+fn populate_analyzed<'a>(text: &'a str, readings: &mut Vec<(Cow<'a, str>, Id)>, id: Id) {
+    fn is_common(phrase: &str) -> bool {
+        match phrase {
+            "to" | "a" | "in" | "of" | "for" | "so" | "if" | "by" | "but" | "not" | "any"
+            | "way" | "into" => true,
+            string if string.starts_with("e.g.") => {
+                if let Some(rest) = string.strip_prefix("e.g. ") {
+                    is_common(rest)
+                } else {
+                    string == "e.g."
+                }
+            }
+            _ => false,
+        }
+    }
+
+    for phrase in analyze_glossary::analyze(text) {
+        if phrase.chars().count() > 32 {
+            continue;
+        }
+
+        // Skip overly common prefixes which would mostly just be
+        // unhelpful to index.
+        if !is_common(phrase) {
+            readings.push((Cow::Borrowed(phrase), id));
+        }
+    }
+}
+
+fn other_readings(
+    output: &mut Vec<(Cow<'_, str>, Id)>,
+    text: &str,
+    a: Id,
+    b: Id,
+    f: for<'a> fn(&'a Segment<'_>) -> &'a str,
+) {
+    let mut romanized = String::new();
+    let mut other = String::new();
+
+    for part in romaji::analyze(text) {
+        romanized += part.romanize();
+        other += f(&part);
+    }
+
+    output.push((Cow::Owned(romanized), a));
+    output.push((Cow::Owned(other), b));
+}
+
+#[derive(Clone)]
+pub struct Database<'a> {
+    header: &'a Header,
+    checksum: u32,
+    bytes: &'a [u8],
+    index: &'a Index,
+    data: &'a Buf,
+}
+
+impl<'a> Database<'a> {
+    /// Construct a new database wrapper.
+    ///
+    /// This validates the magic and format version in the header of the
+    /// database, but doesn't verify its checksum. The database is typically
+    /// memory mapped, and computing the checksum would read all of it into
+    /// memory every time it's opened. Use [`Database::verify`] once, for
+    /// example when a server starts, to check that the database isn't
+    /// corrupt.
+    pub fn new(data: &'a [u8]) -> Result<Self> {
+        if data.len() < size_of::<Header>() + CHECKSUM_SIZE || data[..MAGIC.len()] != MAGIC {
+            bail!("Not a database file, try rebuilding it");
+        }
+
+        let bytes = data;
+        let data = Buf::new(data);
+        let header = data.load(Ref::<Header>::zero())?;
+
+        if header.version != VERSION {
+            bail!(
+                "Database has format version {}, but version {VERSION} is supported, try rebuilding it",
+                header.version
+            );
+        }
+
+        let (_, checksum) = bytes.split_at(bytes.len() - CHECKSUM_SIZE);
+        let checksum = u32::from_le_bytes(checksum.try_into()?);
+        let index = data.load(header.index)?;
+
+        Ok(Self {
+            header,
+            checksum,
+            bytes,
+            index,
+            data,
+        })
+    }
+
+    /// Verify the checksum of the database, which requires reading all of it.
+    pub fn verify(&self) -> Result<()> {
+        let (data, _) = self.bytes.split_at(self.bytes.len() - CHECKSUM_SIZE);
+        let checksum = crc32fast::hash(data);
+
+        if self.checksum != checksum {
+            bail!(
+                "Database checksum mismatch, expected {:08x} but was {checksum:08x}",
+                self.checksum
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Get information about the database.
+    pub fn info(&self) -> Result<Info<'a>> {
+        let load = |string: Ref<str>| -> Result<Option<&'a str>> {
+            let string = self.data.load(string)?;
+            Ok((!string.is_empty()).then_some(string))
+        };
+
+        Ok(Info {
+            version: self.header.version,
+            checksum: self.checksum,
+            built: self.header.built,
+            jmdict_created: load(self.header.jmdict_created)?,
+            kanjidic2_created: load(self.header.kanjidic2_created)?,
+            kanjidic2_version: load(self.header.kanjidic2_version)?,
+        })
+    }
+
+    /// Get identifier by sequence.
+    pub fn lookup_sequence(&self, sequence: u32) -> Result<Option<Id>> {
+        let Some(index) = self.index.by_sequence.get(self.data, &sequence)? else {
+            return Ok(None);
+        };
+
+        Ok(Some(Id::new(*index)))
     }
 
     /// Get an entry from the database.
@@ -514,6 +1345,7 @@ impl<'a> Database<'a> {
 
         Ok(match id.source {
             IndexSource::Kanji { .. } => Entry::Kanji(ENCODING.from_slice(bytes)?),
+            IndexSource::Name => Entry::Name(ENCODING.from_slice(bytes)?),
             _ => Entry::Dict(ENCODING.from_slice(bytes)?),
         })
     }
@@ -552,21 +1384,331 @@ impl<'a> Database<'a> {
         Ok(output)
     }
 
+    /// Find entries which match the given input exactly, either as written or
+    /// as an inflection of their dictionary form.
+    pub fn resolve(&self, input: &str) -> Result<Vec<Id>> {
+        let mut ids = self.lookup(input)?;
+        ids.extend(self.deinflect(input)?);
+        Ok(ids)
+    }
+
+    /// Deinflect the given query and look up every candidate dictionary form
+    /// it could have been inflected from.
+    ///
+    /// Only candidates which correspond to an entry with a matching part of
+    /// speech are returned, identified as inflections of that entry.
+    #[tracing::instrument(skip_all)]
+    pub fn deinflect(&self, query: &str) -> Result<Vec<Id>> {
+        self.deinflect_with(query, &mut Cache::default())
+    }
+
+    fn deinflect_with(&self, query: &str, cache: &mut Cache<'a>) -> Result<Vec<Id>> {
+        let mut output = Vec::new();
+
+        for d in inflection::deinflect(query) {
+            for id in cache.lookup(self, &d.text)?.to_vec() {
+                if !matches!(id.source, IndexSource::Word) {
+                    continue;
+                }
+
+                let Some(entry) = cache.entry(self, id)? else {
+                    continue;
+                };
+
+                let pos = inflection::parts_of_speech(entry);
+
+                if !d.pos.iter().any(|p| pos.contains(p)) {
+                    continue;
+                }
+
+                let Some(reading) = inflection::find_reading(entry, &d.text) else {
+                    continue;
+                };
+
+                output.push(match d.kind {
+                    inflection::Kind::Verb => {
+                        Id::verb_inflection(id.index, reading, d.inflection, d.colloquial)
+                    }
+                    inflection::Kind::Adjective => {
+                        Id::adjective_inflection(id.index, reading, d.inflection, d.colloquial)
+                    }
+                    inflection::Kind::Noun => {
+                        Id::suru_inflection(id.index, reading, d.inflection, d.colloquial)
+                    }
+                });
+            }
+        }
+
+        tracing::trace!(output = output.len());
+        Ok(output)
+    }
+
+    /// Get example sentences illustrating the entry with the given sequence.
+    pub fn examples_for(&self, sequence: u32) -> Result<Examples<'a>> {
+        let refs = match self.index.examples.get(self.data, &sequence)? {
+            Some(refs) => self.data.load(*refs)?,
+            None => &[],
+        };
+
+        Ok(Examples {
+            data: self.data,
+            refs,
+        })
+    }
+
+    /// Find kanji which contain all of the given components, sorted by
+    /// stroke count.
+    #[tracing::instrument(skip_all)]
+    pub fn kanji_by_components(
+        &self,
+        components: &[&str],
+    ) -> Result<Vec<kanjidic2::Character<'a>>> {
+        let mut sets = Vec::with_capacity(components.len());
+
+        for component in components {
+            let Some(kanji) = self.index.by_component.get(self.data, *component)? else {
+                return Ok(Vec::new());
+            };
+
+            sets.push(self.data.load(*kanji)?);
+        }
+
+        // Intersect using the smallest set, which preserves its stroke
+        // count ordering.
+        sets.sort_by_key(|set| set.len());
+
+        let Some((first, rest)) = sets.split_first() else {
+            return Ok(Vec::new());
+        };
+
+        let rest = rest
+            .iter()
+            .map(|set| set.iter().collect::<HashSet<_>>())
+            .collect::<Vec<_>>();
+
+        let mut output = Vec::new();
+
+        for kanji_ref in first.iter() {
+            if !rest.iter().all(|set| set.contains(kanji_ref)) {
+                continue;
+            }
+
+            let id = Id::kanji_reading(*kanji_ref, KanjiReading::Literal);
+
+            if let Entry::Kanji(kanji) = self.get(id)? {
+                output.push(kanji);
+            }
+        }
+
+        tracing::trace!(output = output.len());
+        Ok(output)
+    }
+
     /// Test if db contains the given string.
     pub fn contains(&self, query: &str) -> Result<bool> {
         Ok(self.index.lookup.contains_key(self.data, query)?)
     }
 
-    /// Perform the given search.
-    pub fn search(&self, input: &str) -> Result<Search<'a>> {
+    /// Find readings and kanji which starts with the given prefix, up to
+    /// `limit` distinct keys.
+    #[tracing::instrument(skip_all)]
+    pub fn prefix(&self, prefix: &str, limit: usize) -> Result<Vec<Id>> {
+        self.by_prefix(prefix, limit, |_| true)
+    }
+
+    /// Find readings and kanji which ends with the given suffix, up to
+    /// `limit` distinct keys.
+    #[tracing::instrument(skip_all)]
+    pub fn suffix(&self, suffix: &str, limit: usize) -> Result<Vec<Id>> {
+        let reversed = suffix.chars().rev().collect::<String>();
+        self.by_reversed_suffix(&reversed, limit, |_| true)
+    }
+
+    /// Find readings and kanji matching the given glob pattern, up to `limit`
+    /// distinct keys.
+    ///
+    /// `*` matches any number of characters and `?` matches a single
+    /// character. Input which isn't a pattern performs an exact lookup, and
+    /// patterns which neither start nor end with a literal character are
+    /// rejected.
+    #[tracing::instrument(skip_all)]
+    pub fn glob(&self, input: &str, limit: usize) -> Result<Vec<Id>> {
+        let Some(pattern) = glob::Pattern::new(input) else {
+            return self.lookup(input);
+        };
+
+        if !pattern.is_anchored() {
+            bail!("Pattern `{input}` must start or end with a literal character");
+        }
+
+        let prefix = pattern.prefix();
+        let suffix = pattern.reversed_suffix();
+
+        // Narrow the search using whichever literal part of the pattern is
+        // the longest.
+        if prefix.len() >= suffix.len() {
+            self.by_prefix(&prefix, limit, |key| pattern.is_match(key))
+        } else {
+            self.by_reversed_suffix(&suffix, limit, |key| pattern.is_match(key))
+        }
+    }
+
+    /// Find entries whose english glossary matches the query after
+    /// normalization, tolerating a small number of typos depending on the
+    /// length of the query. Up to `limit` distinct phrases are matched, with
+    /// the closest ones first.
+    #[tracing::instrument(skip_all)]
+    pub fn fuzzy(&self, query: &str, limit: usize) -> Result<Vec<Id>> {
+        let query = english::normalize(query);
+
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let max = english::max_distance(&query);
+        let keys = self.data.load(self.index.english_by_len)?;
+
+        // Phrases are sorted by character count, which is what the edit
+        // distance is measured in, so only the range of lengths which could
+        // possibly be within the distance needs to be inspected.
+        let len = query.chars().count();
+        let min_len = len.saturating_sub(max);
+        let max_len = len + max;
+
+        let start = partition_point(keys, |key| {
+            Ok(self.data.load(*key)?.chars().count() < min_len)
+        })?;
+
+        let mut matches = Vec::new();
+
+        for key in &keys[start..] {
+            let phrase = self.data.load(*key)?;
+
+            if phrase.chars().count() > max_len {
+                break;
+            }
+
+            if let Some(distance) = english::distance(&query, phrase, max) {
+                matches.push((distance, phrase));
+            }
+        }
+
+        matches.sort_by_key(|&(distance, _)| distance);
+        matches.truncate(limit);
+
+        let mut output = Vec::new();
+
+        for (distance, phrase) in matches {
+            if let Some(indexes) = self.index.english.get(self.data, phrase)? {
+                for &index in self.data.load(*indexes)? {
+                    output.push(Id::fuzzy(index, distance as u8));
+                }
+            }
+        }
+
+        tracing::trace!(output = output.len());
+        Ok(output)
+    }
+
+    fn by_prefix(
+        &self,
+        prefix: &str,
+        limit: usize,
+        mut filter: impl FnMut(&str) -> bool,
+    ) -> Result<Vec<Id>> {
+        let sorted = self.data.load(self.index.sorted)?;
+        let start = partition_point(sorted, |key| Ok(self.data.load(*key)? < prefix))?;
+
+        let mut output = Vec::new();
+        let mut count = 0;
+
+        for key in &sorted[start..] {
+            let key = self.data.load(*key)?;
+
+            if count == limit || !key.starts_with(prefix) {
+                break;
+            }
+
+            if filter(key) {
+                output.extend(self.lookup(key)?);
+                count += 1;
+            }
+        }
+
+        tracing::trace!(output = output.len());
+        Ok(output)
+    }
+
+    fn by_reversed_suffix(
+        &self,
+        reversed: &str,
+        limit: usize,
+        mut filter: impl FnMut(&str) -> bool,
+    ) -> Result<Vec<Id>> {
+        let entries = self.data.load(self.index.reversed)?;
+        let start = partition_point(entries, |e| Ok(self.data.load(e.reversed)? < reversed))?;
+
+        let mut output = Vec::new();
+        let mut count = 0;
+
+        for e in &entries[start..] {
+            if count == limit || !self.data.load(e.reversed)?.starts_with(reversed) {
+                break;
+            }
+
+            let key = self.data.load(e.key)?;
+
+            if filter(key) {
+                output.extend(self.lookup(key)?);
+                count += 1;
+            }
+        }
+
+        tracing::trace!(output = output.len());
+        Ok(output)
+    }
+
+    /// Perform the given search, returning at most `limit` entries.
+    ///
+    /// Japanese input containing wildcards is treated as a glob pattern, see
+    /// [`Database::glob`]. Otherwise inflections of the input and entries
+    /// starting with it are included.
+    pub fn search(&self, input: &str, limit: usize) -> Result<Search<'a>> {
         let mut entries = Vec::new();
         let mut characters = Vec::new();
+        let mut names = Vec::new();
         let mut dedup = HashMap::new();
         let mut seen = HashSet::new();
+        let mut seen_names = HashSet::new();
 
         self.populate_kanji(input, &mut seen, &mut characters)?;
 
-        for id in self.lookup(input)? {
+        let ids = if glob::Pattern::new(input).is_some() {
+            self.glob(input, limit)?
+        } else {
+            let mut ids = self.lookup(input)?;
+            ids.extend(self.deinflect(input)?);
+
+            if !input.is_empty() {
+                for mut id in self.prefix(input, limit)? {
+                    // Names and kanji keep their source since it determines
+                    // how the entry is decoded.
+                    if id.source == IndexSource::Word {
+                        id.source = IndexSource::Prefix;
+                    }
+
+                    ids.push(id);
+                }
+            }
+
+            if input.chars().any(|c| c.is_ascii_alphabetic()) {
+                ids.extend(self.fuzzy(input, limit)?);
+            }
+
+            ids
+        };
+
+        for id in ids {
             let entry = match self.get(id)? {
                 Entry::Kanji(kanji) => {
                     if seen.insert(kanji.literal) {
@@ -575,6 +1717,13 @@ impl<'a> Database<'a> {
 
                     continue;
                 }
+                Entry::Name(name) => {
+                    if names.len() < limit && seen_names.insert(id.index()) {
+                        names.push(name);
+                    }
+
+                    continue;
+                }
                 Entry::Dict(entry) => entry,
             };
 
@@ -600,10 +1749,17 @@ impl<'a> Database<'a> {
 
         for (data, e) in &mut entries {
             let inflection = data.sources.iter().any(|index| index.is_inflection());
-            data.key = e.sort_key(input, inflection);
+            data.key = e.sort_key(input, inflection, distance(&data.sources));
         }
 
-        entries.sort_by(|a, b| a.0.key.cmp(&b.0.key));
+        // Entries which only start with the input are ranked after other
+        // matches, so that they never push exact matches past the limit.
+        entries.sort_by(|a, b| {
+            prefix_only(&a.0.sources)
+                .cmp(&prefix_only(&b.0.sources))
+                .then_with(|| a.0.key.cmp(&b.0.key))
+        });
+        entries.truncate(limit);
 
         for (_, entry) in &entries {
             for kanji in &entry.kanji_elements {
@@ -614,6 +1770,7 @@ impl<'a> Database<'a> {
         Ok(Search {
             entries,
             characters,
+            names,
         })
     }
 
@@ -649,6 +1806,161 @@ impl<'a> Database<'a> {
         Ok(())
     }
 
+    /// Find the entry which best matches the given text exactly, either as
+    /// written or through an inflection.
+    fn best_match(
+        &self,
+        text: &str,
+        cache: &mut Cache<'a>,
+    ) -> Result<Option<(EntryResultKey, jmdict::Entry<'a>)>> {
+        let mut ids = cache.lookup(self, text)?.to_vec();
+        ids.extend(self.deinflect_with(text, cache)?);
+
+        let mut sources = BTreeMap::<u32, BTreeSet<IndexSource>>::new();
+
+        for id in ids {
+            if cache.entry(self, id)?.is_some() {
+                sources.entry(id.index()).or_default().insert(id.source());
+            }
+        }
+
+        let mut best = None::<(EntryResultKey, &jmdict::Entry<'a>)>;
+
+        for (index, sources) in sources {
+            let Some(Some(entry)) = cache.entries.get(&index) else {
+                continue;
+            };
+
+            let inflection = sources.iter().any(|s| s.is_inflection());
+            let key = entry.sort_key(text, inflection, distance(&sources));
+
+            if matches!(&best, Some((b, _)) if b.key <= key) {
+                continue;
+            }
+
+            best = Some((
+                EntryResultKey {
+                    index,
+                    key,
+                    sources,
+                },
+                entry,
+            ));
+        }
+
+        Ok(best.map(|(key, entry)| (key, entry.clone())))
+    }
+
+    /// Test if any key in the index starts with the given prefix.
+    fn has_prefix(&self, prefix: &str) -> Result<bool> {
+        let sorted = self.data.load(self.index.sorted)?;
+        let start = partition_point(sorted, |key| Ok(self.data.load(*key)? < prefix))?;
+
+        let Some(key) = sorted.get(start) else {
+            return Ok(false);
+        };
+
+        Ok(self.data.load(*key)?.starts_with(prefix))
+    }
+
+    /// Segment the given text into a sequence of tokens.
+    ///
+    /// This builds a lattice of every dictionary and inflection match in the
+    /// input, and picks the path through it which uses the fewest and best
+    /// weighted tokens. Characters which can't be matched are grouped into
+    /// tokens without an entry.
+    #[tracing::instrument(skip_all)]
+    pub fn segment(&self, input: &str) -> Result<Vec<Token<'a>>> {
+        let bounds = input
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([input.len()])
+            .collect::<Vec<_>>();
+
+        let n = bounds.len() - 1;
+
+        // The best known path to each character boundary, as its total cost,
+        // the boundary the last token started at, and the last token.
+        let mut best = Vec::with_capacity(n + 1);
+        best.push(Some((0.0f32, 0, None)));
+        best.resize_with(n + 1, || None);
+
+        let mut cache = Cache::default();
+
+        for start in 0..n {
+            let Some((cost, _, _)) = best[start] else {
+                continue;
+            };
+
+            let mut edges = vec![(start + 1, cost + UNKNOWN_COST, None)];
+
+            for end in start + 1..=n.min(start + MAX_TOKEN) {
+                let text = &input[bounds[start]..bounds[end]];
+
+                if let Some(m) = self.best_match(text, &mut cache)? {
+                    edges.push((end, cost + 1.0 + 1.0 / m.0.key.weight(), Some(m)));
+                }
+
+                // Longer spans can only match if some dictionary form starts
+                // with this one, unless it might end in an inflected suffix.
+                if let Some(stems) = inflection::stems(text) {
+                    let mut viable = false;
+
+                    for stem in stems {
+                        viable |= self.has_prefix(stem)?;
+                    }
+
+                    if !viable {
+                        break;
+                    }
+                }
+            }
+
+            for (end, cost, m) in edges {
+                if matches!(best[end], Some((c, _, _)) if c <= cost) {
+                    continue;
+                }
+
+                best[end] = Some((cost, start, m));
+            }
+        }
+
+        let mut tokens = Vec::new();
+        let mut end = n;
+
+        while end > 0 {
+            let Some((_, start, m)) = best[end].take() else {
+                return Err(anyhow!("Missing path to offset {}", bounds[end]));
+            };
+
+            match (m, tokens.last_mut()) {
+                // Merge with a following unmatched token.
+                (
+                    None,
+                    Some(Token {
+                        start: next,
+                        entry: None,
+                        ..
+                    }),
+                ) => {
+                    *next = bounds[start];
+                }
+                (m, _) => {
+                    tokens.push(Token {
+                        start: bounds[start],
+                        end: bounds[end],
+                        entry: m,
+                    });
+                }
+            }
+
+            end = start;
+        }
+
+        tokens.reverse();
+        Ok(tokens)
+    }
+
     /// Analyze the given string, looking it up in the database and returning
     /// all prefix matching entries and their texts.
     pub fn analyze(&self, q: &str, start: usize) -> BTreeMap<EntryKey, String> {
@@ -671,12 +1983,22 @@ impl<'a> Database<'a> {
                 }
             };
 
+            let deinflected = match self.deinflect(it.as_str()) {
+                Ok(deinflected) => deinflected,
+                Err(error) => {
+                    log::error!("Deinflection failed: {error}");
+                    Vec::new()
+                }
+            };
+
+            let lookup = lookup.into_iter().chain(deinflected);
+
             for id in lookup {
                 let Ok(Entry::Dict(e)) = self.get(id) else {
                     continue;
                 };
 
-                let a = e.sort_key(it.as_str(), id.source().is_inflection());
+                let a = e.sort_key(it.as_str(), id.source().is_inflection(), 0);
 
                 if let Some(b) = sort_key.take() {
                     sort_key = Some(a.min(b));
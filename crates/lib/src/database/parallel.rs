@@ -0,0 +1,58 @@
+//! Helper to process chunks of input on multiple threads.
+
+use std::num::NonZeroUsize;
+use std::panic;
+use std::thread;
+
+use anyhow::Result;
+
+/// Process items produced by `next` with `process` on multiple threads,
+/// calling `merge` with each result in the same order as the items were
+/// produced.
+///
+/// Since results are merged in order, the outcome doesn't depend on how many
+/// threads are used.
+pub(super) fn process<I, T>(
+    mut next: impl FnMut() -> Result<Option<I>>,
+    process: impl Fn(&I) -> Result<T> + Sync,
+    mut merge: impl FnMut(T) -> Result<()>,
+) -> Result<()>
+where
+    I: Sync,
+    T: Send,
+{
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let mut batch = Vec::with_capacity(threads);
+
+    loop {
+        batch.clear();
+
+        while batch.len() < threads {
+            let Some(item) = next()? else {
+                break;
+            };
+
+            batch.push(item);
+        }
+
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let results = thread::scope(|s| {
+            let handles = batch
+                .iter()
+                .map(|item| s.spawn(|| process(item)))
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+                .collect::<Vec<_>>()
+        });
+
+        for result in results {
+            merge(result?)?;
+        }
+    }
+}
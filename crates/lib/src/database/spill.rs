@@ -0,0 +1,291 @@
+//! Collects index keys while building the database, spilling them to sorted
+//! runs on disk so that memory use stays bounded.
+
+use std::borrow::Cow;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::{mem, process};
+
+use anyhow::{Context, Result};
+use musli::{Decode, Encode};
+
+use super::{Id, IndexSource, ENCODING};
+
+/// The number of keys kept in memory before they are spilled to disk.
+const THRESHOLD: usize = 1 << 21;
+
+/// Sort key used for index keys, where longer keys are ordered first so that
+/// shorter keys can reuse them as substrings.
+fn order(key: &str) -> (Reverse<usize>, &str) {
+    (Reverse(key.chars().count()), key)
+}
+
+#[derive(Encode, Decode)]
+#[musli(packed)]
+struct Record {
+    key: String,
+    index: u32,
+    source: IndexSource,
+}
+
+/// A sorted run stored on disk, which is removed once dropped.
+struct Run {
+    path: PathBuf,
+    reader: BufReader<File>,
+    buf: Vec<u8>,
+}
+
+impl Run {
+    fn next(&mut self) -> Result<Option<(String, Id)>> {
+        let mut len = [0; 4];
+
+        match self.reader.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+
+        self.buf.resize(u32::from_le_bytes(len) as usize, 0);
+        self.reader.read_exact(&mut self.buf)?;
+        let record: Record = ENCODING.from_slice(&self.buf)?;
+
+        let id = Id {
+            index: record.index,
+            source: record.source,
+        };
+
+        Ok(Some((record.key, id)))
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Collection of index keys.
+pub(super) struct Spill {
+    pairs: Vec<(String, Id)>,
+    runs: Vec<Run>,
+    len: usize,
+    threshold: usize,
+}
+
+impl Spill {
+    pub(super) fn new() -> Self {
+        Self::with_threshold(THRESHOLD)
+    }
+
+    pub(super) fn with_threshold(threshold: usize) -> Self {
+        Self {
+            pairs: Vec::new(),
+            runs: Vec::new(),
+            len: 0,
+            threshold,
+        }
+    }
+
+    /// Add the given keys.
+    pub(super) fn extend<'a, I>(&mut self, iter: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (Cow<'a, str>, Id)>,
+    {
+        for (key, id) in iter {
+            self.pairs.push((key.into_owned(), id));
+            self.len += 1;
+
+            if self.pairs.len() >= self.threshold {
+                self.spill()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The total number of keys collected.
+    pub(super) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Write the keys currently in memory to a sorted run.
+    fn spill(&mut self) -> Result<()> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let mut pairs = mem::take(&mut self.pairs);
+        pairs.sort_by(|a, b| order(&a.0).cmp(&order(&b.0)));
+
+        let n = COUNTER.fetch_add(1, AtomicOrdering::Relaxed);
+        let path = std::env::temp_dir().join(format!("jpv-spill-{}-{n}.bin", process::id()));
+
+        tracing::info!("Spilling {} keys to {}", pairs.len(), path.display());
+
+        let file = File::options()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .with_context(|| path.display().to_string())?;
+
+        let mut writer = BufWriter::new(file);
+        let mut output = Vec::new();
+
+        for (key, id) in pairs {
+            let record = Record {
+                key,
+                index: id.index,
+                source: id.source,
+            };
+
+            output.clear();
+            ENCODING.to_writer(&mut output, &record)?;
+            writer.write_all(&(output.len() as u32).to_le_bytes())?;
+            writer.write_all(&output)?;
+        }
+
+        writer.flush()?;
+        drop(writer);
+
+        let file = File::open(&path).with_context(|| path.display().to_string())?;
+
+        self.runs.push(Run {
+            path,
+            reader: BufReader::new(file),
+            buf: Vec::new(),
+        });
+
+        Ok(())
+    }
+
+    /// Finish collecting keys, returning them merged in sorted order.
+    pub(super) fn finish(mut self) -> Result<Merge> {
+        self.pairs.sort_by(|a, b| order(&a.0).cmp(&order(&b.0)));
+
+        let mut merge = Merge {
+            runs: self.runs,
+            memory: self.pairs.into_iter(),
+            heap: BinaryHeap::new(),
+        };
+
+        for index in 0..=merge.runs.len() {
+            merge.advance(index)?;
+        }
+
+        Ok(merge)
+    }
+}
+
+/// The head of a run being merged.
+struct Head {
+    key: String,
+    id: Id,
+    run: usize,
+}
+
+impl Head {
+    fn order(&self) -> (Reverse<usize>, &str, usize) {
+        let (len, key) = order(&self.key);
+        (len, key, self.run)
+    }
+}
+
+impl PartialEq for Head {
+    fn eq(&self, other: &Self) -> bool {
+        self.order() == other.order()
+    }
+}
+
+impl Eq for Head {}
+
+impl PartialOrd for Head {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Head {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.order().cmp(&other.order())
+    }
+}
+
+/// Iterator over keys merged from all runs in sorted order, where keys from
+/// earlier runs are ordered first.
+pub(super) struct Merge {
+    runs: Vec<Run>,
+    memory: std::vec::IntoIter<(String, Id)>,
+    heap: BinaryHeap<Reverse<Head>>,
+}
+
+impl Merge {
+    /// Advance the run with the given index, where the run after the last
+    /// one on disk refers to the keys which were kept in memory.
+    fn advance(&mut self, run: usize) -> Result<()> {
+        let next = match self.runs.get_mut(run) {
+            Some(r) => r.next()?,
+            None => self.memory.next(),
+        };
+
+        if let Some((key, id)) = next {
+            self.heap.push(Reverse(Head { key, id, run }));
+        }
+
+        Ok(())
+    }
+
+    /// Get the next key in sorted order.
+    pub(super) fn next(&mut self) -> Result<Option<(String, Id)>> {
+        let Some(Reverse(head)) = self.heap.pop() else {
+            return Ok(None);
+        };
+
+        self.advance(head.run)?;
+        Ok(Some((head.key, head.id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{Id, Spill};
+
+    #[test]
+    fn test_merge() {
+        let mut spill = Spill::with_threshold(2);
+
+        let keys = ["b", "abc", "a", "bc", "a", "c"];
+
+        spill
+            .extend(
+                keys.iter()
+                    .enumerate()
+                    .map(|(i, k)| (Cow::Borrowed(*k), Id::new(i as u32))),
+            )
+            .unwrap();
+
+        assert_eq!(spill.len(), keys.len());
+
+        let mut merge = spill.finish().unwrap();
+        let mut output = Vec::new();
+
+        while let Some((key, id)) = merge.next().unwrap() {
+            output.push((key, id.index()));
+        }
+
+        assert_eq!(
+            output,
+            [
+                (String::from("abc"), 1),
+                (String::from("bc"), 3),
+                (String::from("a"), 2),
+                (String::from("a"), 4),
+                (String::from("b"), 0),
+                (String::from("c"), 5),
+            ]
+        );
+    }
+}
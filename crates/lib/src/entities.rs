@@ -11,7 +11,7 @@ macro_rules! entity {
         $(<$variant:ident $entity:literal $doc:literal>)*
     }) => {
         $(#[$($meta)*])*
-        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key, ZeroCopy, Visit)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Key, ZeroCopy, Visit)]
         #[key(bitset)]
         #[repr(u8)]
         $vis enum $name {
@@ -244,6 +244,38 @@ entity! {
     }
 }
 
+entity! {
+    #[derive(Encode, Decode, Serialize, Deserialize)]
+    pub enum NameType {
+        <Character "char" "character">
+        <Company "company" "company name">
+        <Creature "creat" "creature">
+        <Deity "dei" "deity">
+        <Document "doc" "document">
+        <Event "ev" "event">
+        <Female "fem" "female given name or forename">
+        <Fiction "fict" "fiction">
+        <Given "given" "given name or forename, gender not specified">
+        <Group "group" "group">
+        <Legend "leg" "legend">
+        <Male "masc" "male given name or forename">
+        <Mythology "myth" "mythology">
+        <Object "obj" "object">
+        <Organization "organization" "organization name">
+        <Other "oth" "other">
+        <Person "person" "full name of a particular person">
+        <Place "place" "place name">
+        <Product "product" "product name">
+        <Religion "relig" "religion">
+        <Service "serv" "service">
+        <Ship "ship" "ship name">
+        <Station "station" "railway station">
+        <Surname "surname" "family or surname">
+        <Unclassified "unclass" "unclassified name">
+        <Work "work" "work of art, literature, music, etc. name">
+    }
+}
+
 entity! {
     #[derive(Encode, Decode, Serialize, Deserialize)]
     pub enum Dialect {
@@ -1,9 +1,15 @@
 #[cfg(test)]
 mod tests;
 
+mod align;
+pub(crate) use self::align::{align, Readings};
+pub use self::align::{Alignment, OwnedAlignment};
+
 use core::fmt;
 use std::slice;
 
+use serde::Serialize;
+
 use crate::concat::{self, Concat};
 
 /// An iterator over furigana groups.
@@ -11,6 +17,7 @@ pub struct Furigana<'a, const N: usize, const S: usize> {
     kanji: Concat<'a, N>,
     reading: Concat<'a, N>,
     suffix: Concat<'a, S>,
+    alignment: &'a [u8],
 }
 
 impl<'a> Furigana<'a, 1, 1> {
@@ -21,6 +28,7 @@ impl<'a> Furigana<'a, 1, 1> {
             kanji: Concat::new([kanji]),
             reading: Concat::new([reading]),
             suffix: Concat::new([suffix]),
+            alignment: &[],
         }
     }
 }
@@ -35,15 +43,26 @@ impl<'a, const N: usize, const S: usize> Furigana<'a, N, S> {
             kanji,
             reading,
             suffix,
+            alignment: &[],
         }
     }
 
+    /// Split groups of kanji according to the given per-kanji reading
+    /// lengths, as stored in an [`Alignment`].
+    ///
+    /// Groups whose reading doesn't add up to the aligned lengths are left as
+    /// they are.
+    pub fn with_alignment(self, alignment: &'a [u8]) -> Self {
+        Self { alignment, ..self }
+    }
+
     /// Construct an iterator over furigana groups.
     pub fn iter(&self) -> Iter<'_, 'a, N, S> {
         Iter::new(
             self.kanji.as_slice(),
             self.reading.as_slice(),
             self.suffix.clone(),
+            self.alignment,
         )
     }
 
@@ -92,7 +111,8 @@ fn is_kanji(c: char) -> bool {
 }
 
 /// A single furigana group.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum FuriganaGroup<'a> {
     /// Kanji with associated kana, such as `私[わたし]`.
     Kanji(&'a str, &'a str),
@@ -106,17 +126,32 @@ pub struct Iter<'this, 'a, const N: usize, const S: usize> {
     current: Option<(&'a str, &'a str)>,
     kana: Option<&'a str>,
     suffix: concat::IntoIter<'a, S>,
+    alignment: &'a [u8],
+    /// The number of kanji which have been emitted, used to index into the
+    /// alignment.
+    index: usize,
+    /// A kanji group which is in the process of being split.
+    split: Option<(&'a str, &'a str)>,
 }
 
 impl<'this, 'a, const N: usize, const S: usize> Iterator for Iter<'this, 'a, N, S> {
     type Item = FuriganaGroup<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(group) = self.split() {
+            return Some(group);
+        }
+
         if let Some(kana) = self.kana.take() {
             return Some(FuriganaGroup::Kana(kana));
         }
 
         if let Some(group) = self.group() {
+            if let FuriganaGroup::Kanji(kanji, reading) = group {
+                self.split = Some((kanji, reading));
+                return self.split();
+            }
+
             return Some(group);
         }
 
@@ -126,19 +161,73 @@ impl<'this, 'a, const N: usize, const S: usize> Iterator for Iter<'this, 'a, N,
 }
 
 impl<'this, 'a, const N: usize, const S: usize> Iter<'this, 'a, N, S> {
-    fn new(kanji: &'this [&'a str], reading: &'this [&'a str], suffix: Concat<'a, S>) -> Self {
+    fn new(
+        kanji: &'this [&'a str],
+        reading: &'this [&'a str],
+        suffix: Concat<'a, S>,
+        alignment: &'a [u8],
+    ) -> Self {
         let mut this = Self {
             kanji: kanji.iter(),
             reading: reading.iter(),
             current: None,
             kana: None,
             suffix: suffix.into_iter(),
+            alignment,
+            index: 0,
+            split: None,
         };
 
         this.current = this.advance();
         this
     }
 
+    /// Emit the next part of a kanji group which is being split according to
+    /// the alignment.
+    fn split(&mut self) -> Option<FuriganaGroup<'a>> {
+        let (kanji, reading) = self.split.take()?;
+        let count = kanji.chars().count();
+
+        let lengths = self
+            .alignment
+            .get(self.index..self.index + count)
+            .filter(|lengths| {
+                lengths.iter().map(|&n| usize::from(n)).sum::<usize>() == reading.chars().count()
+            });
+
+        let Some(lengths) = lengths else {
+            self.index += count;
+            return Some(FuriganaGroup::Kanji(kanji, reading));
+        };
+
+        // Take kanji up until the first one with a non-zero length, since
+        // zeroes are grouped with the kanji that follows them.
+        let Some(n) = lengths.iter().position(|&n| n != 0) else {
+            self.index += count;
+            return Some(FuriganaGroup::Kanji(kanji, reading));
+        };
+
+        let k = kanji
+            .char_indices()
+            .nth(n + 1)
+            .map_or(kanji.len(), |(i, _)| i);
+
+        let r = reading
+            .char_indices()
+            .nth(usize::from(lengths[n]))
+            .map_or(reading.len(), |(i, _)| i);
+
+        let (group_kanji, kanji) = kanji.split_at(k);
+        let (group_reading, reading) = reading.split_at(r);
+        self.index += n + 1;
+
+        if !kanji.is_empty() {
+            self.split = Some((kanji, reading));
+        }
+
+        Some(FuriganaGroup::Kanji(group_kanji, group_reading))
+    }
+
     fn advance(&mut self) -> Option<(&'a str, &'a str)> {
         Some((self.kanji.next()?, self.reading.next()?))
     }
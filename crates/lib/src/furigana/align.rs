@@ -0,0 +1,327 @@
+//! Alignment of a reading to the individual kanji of a word, so that 取り扱い
+//! can be displayed as 取[と]り扱[あつか]い instead of using a single group.
+
+use std::collections::HashMap;
+
+use musli::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+use crate::furigana::is_kanji;
+use crate::kanjidic2::Character;
+
+/// The maximum number of kanji runs which may fall back to being grouped as a
+/// whole before we give up on aligning a word.
+const MAX_GROUPED: usize = 2;
+
+/// The per-kanji alignment of a reading to one of the kanji elements of an
+/// entry.
+#[borrowme::borrowme]
+#[derive(Clone, Debug, Serialize, Deserialize, Encode, Decode)]
+#[musli(packed)]
+pub struct Alignment<'a> {
+    /// The kanji element the alignment applies to.
+    pub kanji: &'a str,
+    /// The number of reading characters of each kanji in order, where `0`
+    /// means that the kanji is grouped with the one following it.
+    pub lengths: Vec<u8>,
+}
+
+/// A single way a kanji can be read.
+struct Variant {
+    kana: Box<[char]>,
+    /// If the variant can appear at the start of a word, which isn't the case
+    /// for rendaku.
+    initial: bool,
+}
+
+/// Known readings of kanji, as collected from kanjidic2.
+#[derive(Default)]
+pub(crate) struct Readings {
+    by_kanji: HashMap<char, Vec<Variant>>,
+}
+
+impl Readings {
+    /// Construct an empty collection of readings.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Test if no readings have been added.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.by_kanji.is_empty()
+    }
+
+    /// Add the on and kun readings of the given kanjidic2 character.
+    pub(crate) fn insert(&mut self, character: &Character<'_>) {
+        let mut chars = character.literal.chars();
+
+        let (Some(literal), None) = (chars.next(), chars.next()) else {
+            return;
+        };
+
+        for reading in &character.reading_meaning.readings {
+            self.insert_reading(literal, reading.ty, reading.text);
+        }
+    }
+
+    /// Add a single reading of the given type, such as `ja_on` or `ja_kun`.
+    pub(crate) fn insert_reading(&mut self, literal: char, ty: &str, text: &str) {
+        let variants = self.by_kanji.entry(literal).or_default();
+
+        match ty {
+            "ja_on" => {
+                add(variants, text.chars().map(hiragana).collect());
+            }
+            "ja_kun" => {
+                let text = text.trim_matches('-');
+
+                if let Some((stem, _)) = text.split_once('.') {
+                    add(variants, stem.chars().collect());
+                }
+
+                add(variants, text.chars().filter(|&c| c != '.').collect());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Add a reading along with its rendaku and geminated variants.
+fn add(variants: &mut Vec<Variant>, kana: Vec<char>) {
+    let Some((&first, rest)) = kana.split_first() else {
+        return;
+    };
+
+    let mut push = |kana: Vec<char>, initial: bool| {
+        if let Some(v) = variants.iter_mut().find(|v| *v.kana == *kana) {
+            v.initial |= initial;
+        } else {
+            variants.push(Variant {
+                kana: kana.into(),
+                initial,
+            });
+        }
+    };
+
+    let mut forms = vec![(kana.clone(), true)];
+
+    for &voiced in rendaku(first) {
+        let mut kana = vec![voiced];
+        kana.extend_from_slice(rest);
+        forms.push((kana, false));
+    }
+
+    for (kana, initial) in forms {
+        if kana.len() > 1 && matches!(kana.last(), Some('く' | 'き' | 'つ' | 'ち')) {
+            let mut geminated = kana.clone();
+            geminated.pop();
+            geminated.push('っ');
+            push(geminated, initial);
+        }
+
+        push(kana, initial);
+    }
+}
+
+/// The voiced forms a kana might take when it appears inside of a compound,
+/// such as 本 being read ぼん in 日本 or ぽん in 一本.
+fn rendaku(c: char) -> &'static [char] {
+    match c {
+        'か' => &['が'],
+        'き' => &['ぎ'],
+        'く' => &['ぐ'],
+        'け' => &['げ'],
+        'こ' => &['ご'],
+        'さ' => &['ざ'],
+        'し' => &['じ'],
+        'す' => &['ず'],
+        'せ' => &['ぜ'],
+        'そ' => &['ぞ'],
+        'た' => &['だ'],
+        'ち' => &['ぢ', 'じ'],
+        'つ' => &['づ', 'ず'],
+        'て' => &['で'],
+        'と' => &['ど'],
+        'は' => &['ば', 'ぱ'],
+        'ひ' => &['び', 'ぴ'],
+        'ふ' => &['ぶ', 'ぷ'],
+        'へ' => &['べ', 'ぺ'],
+        'ほ' => &['ぼ', 'ぽ'],
+        _ => &[],
+    }
+}
+
+/// Convert katakana into hiragana, leaving everything else as-is.
+fn hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        c => c,
+    }
+}
+
+enum Segment {
+    Kana(Vec<char>),
+    Kanji(Vec<char>),
+}
+
+/// Align the given reading to each kanji in `kanji`.
+///
+/// Returns `None` if the word has no runs of multiple kanji, if no run could
+/// be split or if the alignment is ambiguous, in which case the furigana should
+/// be grouped as usual.
+pub(crate) fn align(kanji: &str, reading: &str, readings: &Readings) -> Option<Vec<u8>> {
+    let mut segments = Vec::<Segment>::new();
+
+    for c in kanji.chars() {
+        let kanji = is_kanji(c);
+
+        if !kanji && !crate::romaji::is_hiragana(c) && !crate::romaji::is_katakana(c) {
+            return None;
+        }
+
+        match segments.last_mut() {
+            Some(Segment::Kanji(run)) if kanji => run.push(c),
+            Some(Segment::Kana(run)) if !kanji => run.push(hiragana(c)),
+            _ if kanji => segments.push(Segment::Kanji(vec![c])),
+            _ => segments.push(Segment::Kana(vec![hiragana(c)])),
+        }
+    }
+
+    if !segments
+        .iter()
+        .any(|s| matches!(s, Segment::Kanji(run) if run.len() > 1))
+    {
+        return None;
+    }
+
+    let reading = reading.chars().map(hiragana).collect::<Vec<_>>();
+
+    for grouped in 0..=MAX_GROUPED {
+        let mut search = Search {
+            readings,
+            all: &segments,
+            lengths: Vec::new(),
+            solutions: Vec::new(),
+        };
+
+        search.segments(&segments, &reading, grouped, true);
+        search.solutions.sort();
+        search.solutions.dedup();
+
+        match search.solutions.as_slice() {
+            [] => continue,
+            [(lengths, true)] => return Some(lengths.clone()),
+            _ => return None,
+        }
+    }
+
+    None
+}
+
+struct Search<'a> {
+    readings: &'a Readings,
+    all: &'a [Segment],
+    lengths: Vec<u8>,
+    /// Solutions, and whether any run of multiple kanji was split in them.
+    solutions: Vec<(Vec<u8>, bool)>,
+}
+
+impl Search<'_> {
+    fn segments(&mut self, segments: &[Segment], reading: &[char], grouped: usize, initial: bool) {
+        let Some((segment, rest)) = segments.split_first() else {
+            if reading.is_empty() {
+                self.solutions.push((self.lengths.clone(), self.is_split()));
+            }
+
+            return;
+        };
+
+        match segment {
+            Segment::Kana(kana) => {
+                if let Some(reading) = reading.strip_prefix(kana.as_slice()) {
+                    self.segments(rest, reading, grouped, false);
+                }
+            }
+            Segment::Kanji(run) => {
+                self.run(run, rest, reading, grouped, initial);
+
+                if grouped == 0 {
+                    return;
+                }
+
+                let start = self.lengths.len();
+
+                for n in 1..=reading.len().min(u8::MAX as usize) {
+                    self.lengths.extend(run.iter().skip(1).map(|_| 0));
+                    self.lengths.push(n as u8);
+                    self.segments(rest, &reading[n..], grouped - 1, false);
+                    self.lengths.truncate(start);
+                }
+            }
+        }
+    }
+
+    fn run(
+        &mut self,
+        run: &[char],
+        rest: &[Segment],
+        reading: &[char],
+        grouped: usize,
+        initial: bool,
+    ) {
+        let Some((c, tail)) = run.split_first() else {
+            self.segments(rest, reading, grouped, false);
+            return;
+        };
+
+        let Some(variants) = self.readings.by_kanji.get(c) else {
+            return;
+        };
+
+        for variant in variants {
+            if initial && !variant.initial {
+                continue;
+            }
+
+            let Some(reading) = reading.strip_prefix(&*variant.kana) else {
+                continue;
+            };
+
+            let Ok(n) = u8::try_from(variant.kana.len()) else {
+                continue;
+            };
+
+            self.lengths.push(n);
+            self.run(tail, rest, reading, grouped, false);
+            self.lengths.pop();
+        }
+    }
+
+    /// Test if any kanji in the current solution is aligned on its own while
+    /// being part of a run of multiple kanji.
+    fn is_split(&self) -> bool {
+        let mut lengths = self.lengths.as_slice();
+
+        for segment in self.all {
+            let Segment::Kanji(run) = segment else {
+                continue;
+            };
+
+            if lengths.len() < run.len() {
+                return false;
+            }
+
+            let (run, rest) = lengths.split_at(run.len());
+
+            if let [grouped @ .., _] = run {
+                if grouped.iter().any(|&n| n != 0) {
+                    return true;
+                }
+            }
+
+            lengths = rest;
+        }
+
+        false
+    }
+}
@@ -1,6 +1,6 @@
 use crate::furigana::FuriganaGroup;
 
-use super::Furigana;
+use super::{align, Furigana, Readings};
 
 #[test]
 fn test_mixed_furigana() {
@@ -51,3 +51,76 @@ fn test_trailing_kana() {
         ]
     );
 }
+
+fn readings() -> Readings {
+    let mut readings = Readings::new();
+
+    for (literal, ty, text) in [
+        ('取', "ja_on", "シュ"),
+        ('取', "ja_kun", "と.る"),
+        ('扱', "ja_kun", "あつか.い"),
+        ('扱', "ja_kun", "あつか.う"),
+        ('学', "ja_on", "ガク"),
+        ('学', "ja_kun", "まな.ぶ"),
+        ('校', "ja_on", "コウ"),
+        ('日', "ja_on", "ニチ"),
+        ('日', "ja_kun", "ひ"),
+        ('本', "ja_on", "ホン"),
+        ('手', "ja_kun", "て"),
+        ('紙', "ja_kun", "かみ"),
+        ('上', "ja_on", "ジョウ"),
+        ('上', "ja_kun", "うえ"),
+        ('手', "ja_on", "シュ"),
+    ] {
+        readings.insert_reading(literal, ty, text);
+    }
+
+    readings
+}
+
+#[test]
+fn test_align() {
+    let readings = readings();
+
+    assert_eq!(
+        align("取り扱い", "とりあつかい", &readings),
+        None,
+        "single kanji runs need no alignment"
+    );
+    assert_eq!(align("学校", "がっこう", &readings), Some(vec![2, 2]));
+    assert_eq!(align("手紙", "てがみ", &readings), Some(vec![1, 2]));
+    assert_eq!(align("取扱い", "とりあつかい", &readings), None);
+    assert_eq!(align("日本", "にほん", &readings), None);
+    assert_eq!(
+        align("日本の学校", "にほんのがっこう", &readings),
+        Some(vec![0, 3, 2, 2])
+    );
+    // Readings which don't follow the readings of the individual kanji are
+    // grouped as usual.
+    assert_eq!(align("上手", "じょうず", &readings), None);
+}
+
+#[test]
+fn test_aligned_furigana() {
+    let alignment = [2, 2];
+    let furigana = Furigana::new("学校", "がっこう", "").with_alignment(&alignment);
+    assert_eq!(furigana.to_string(), "学[がっ]校[こう]");
+
+    let alignment = [0, 3, 2, 2];
+    let furigana = Furigana::new("日本の学校", "にほんのがっこう", "").with_alignment(&alignment);
+
+    assert_eq!(
+        furigana.iter().collect::<Vec<_>>(),
+        &[
+            FuriganaGroup::Kanji("日本", "にほん"),
+            FuriganaGroup::Kana("の"),
+            FuriganaGroup::Kanji("学", "がっ"),
+            FuriganaGroup::Kanji("校", "こう"),
+        ]
+    );
+
+    // An alignment which doesn't add up is ignored.
+    let alignment = [1, 1];
+    let furigana = Furigana::new("学校", "がっこう", "").with_alignment(&alignment);
+    assert_eq!(furigana.to_string(), "学校[がっこう]");
+}
@@ -0,0 +1,197 @@
+//! Tables for classical and archaic verb classes.
+//!
+//! Classical verbs are conjugated from six bases, each of which is made up of
+//! the kana replacing the last kana of the dictionary form and an optional
+//! trailing kana, like the attributive 起くる of the kami nidan verb 起く.
+
+use crate::PartOfSpeech;
+
+/// A single base, as the kana replacing the last kana of the dictionary form
+/// followed by any trailing kana.
+pub(crate) type Base = [&'static str; 2];
+
+/// The bases of a classical verb.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Bases {
+    /// Irrealis base (未然形).
+    pub(super) mizen: Base,
+    /// Continuative base (連用形).
+    pub(super) renyou: Base,
+    /// Terminal base (終止形), which is also the dictionary form.
+    pub(super) shuushi: Base,
+    /// Attributive base (連体形).
+    pub(super) rentai: Base,
+    /// Realis base (已然形).
+    pub(super) izen: Base,
+    /// Imperative base (命令形).
+    pub(super) meirei: Base,
+    /// The base which べし attaches to.
+    pub(super) beshi: Base,
+}
+
+impl Bases {
+    /// The kana the dictionary form ends in.
+    pub(super) fn ending(&self) -> char {
+        self.shuushi[0].chars().next().unwrap_or_default()
+    }
+}
+
+/// A row of the kana table, as its u, a, i and e kana.
+struct Row {
+    u: &'static str,
+    a: &'static str,
+    i: &'static str,
+    e: &'static str,
+}
+
+macro_rules! rows {
+    ($($name:ident => $u:literal $a:literal $i:literal $e:literal),* $(,)?) => {
+        $(static $name: Row = Row { u: $u, a: $a, i: $i, e: $e };)*
+    }
+}
+
+rows! {
+    A => "う" "あ" "い" "え",
+    KA => "く" "か" "き" "け",
+    GA => "ぐ" "が" "ぎ" "げ",
+    SA => "す" "さ" "し" "せ",
+    ZA => "ず" "ざ" "じ" "ぜ",
+    TA => "つ" "た" "ち" "て",
+    DA => "づ" "だ" "ぢ" "で",
+    NA => "ぬ" "な" "に" "ね",
+    HA => "ふ" "は" "ひ" "へ",
+    BA => "ぶ" "ば" "び" "べ",
+    MA => "む" "ま" "み" "め",
+    YA => "ゆ" "や" "い" "え",
+    RA => "る" "ら" "り" "れ",
+    WA => "う" "わ" "ゐ" "ゑ",
+}
+
+const fn yodan(row: &'static Row) -> Bases {
+    Bases {
+        mizen: [row.a, ""],
+        renyou: [row.i, ""],
+        shuushi: [row.u, ""],
+        rentai: [row.u, ""],
+        izen: [row.e, ""],
+        meirei: [row.e, ""],
+        beshi: [row.u, ""],
+    }
+}
+
+const fn kami_nidan(row: &'static Row) -> Bases {
+    Bases {
+        mizen: [row.i, ""],
+        renyou: [row.i, ""],
+        shuushi: [row.u, ""],
+        rentai: [row.u, "る"],
+        izen: [row.u, "れ"],
+        meirei: [row.i, "よ"],
+        beshi: [row.u, ""],
+    }
+}
+
+const fn shimo_nidan(row: &'static Row) -> Bases {
+    Bases {
+        mizen: [row.e, ""],
+        renyou: [row.e, ""],
+        shuushi: [row.u, ""],
+        rentai: [row.u, "る"],
+        izen: [row.u, "れ"],
+        meirei: [row.e, "よ"],
+        beshi: [row.u, ""],
+    }
+}
+
+/// The irregular nu verb, such as 往ぬ.
+static NU: Bases = Bases {
+    mizen: ["な", ""],
+    renyou: ["に", ""],
+    shuushi: ["ぬ", ""],
+    rentai: ["ぬ", "る"],
+    izen: ["ぬ", "れ"],
+    meirei: ["ね", ""],
+    beshi: ["ぬ", ""],
+};
+
+/// The irregular ri verb, such as 有り, where べし attaches to the
+/// attributive base.
+static RI: Bases = Bases {
+    mizen: ["ら", ""],
+    renyou: ["り", ""],
+    shuushi: ["り", ""],
+    rentai: ["る", ""],
+    izen: ["れ", ""],
+    meirei: ["れ", ""],
+    beshi: ["る", ""],
+};
+
+/// The su verb, which is the precursor to the modern suru.
+static SU: Bases = Bases {
+    mizen: ["せ", ""],
+    renyou: ["し", ""],
+    shuushi: ["す", ""],
+    rentai: ["す", "る"],
+    izen: ["す", "れ"],
+    meirei: ["せ", "よ"],
+    beshi: ["す", ""],
+};
+
+/// The zuru verb, whose bases replace the trailing ずる of the modern
+/// dictionary form.
+pub(super) static ZURU: Bases = Bases {
+    mizen: ["ぜ", ""],
+    renyou: ["じ", ""],
+    shuushi: ["ず", ""],
+    rentai: ["ず", "る"],
+    izen: ["ず", "れ"],
+    meirei: ["ぜ", "よ"],
+    beshi: ["ず", ""],
+};
+
+/// Get the classical table for the given part of speech, if it has one.
+///
+/// Zuru verbs are not included, since their dictionary form is the modern
+/// one, see [`ZURU`].
+pub(super) fn of(pos: PartOfSpeech) -> Option<Bases> {
+    let table = match pos {
+        PartOfSpeech::VerbYodanK => yodan(&KA),
+        PartOfSpeech::VerbYodanG => yodan(&GA),
+        PartOfSpeech::VerbYodanS => yodan(&SA),
+        PartOfSpeech::VerbYodanT => yodan(&TA),
+        PartOfSpeech::VerbYodanN => yodan(&NA),
+        PartOfSpeech::VerbYodanH => yodan(&HA),
+        PartOfSpeech::VerbYodanB => yodan(&BA),
+        PartOfSpeech::VerbYodanM => yodan(&MA),
+        PartOfSpeech::VerbYodanR => yodan(&RA),
+        PartOfSpeech::VerbNidanBK => kami_nidan(&BA),
+        PartOfSpeech::VerbNidanDK => kami_nidan(&DA),
+        PartOfSpeech::VerbNidanGK => kami_nidan(&GA),
+        PartOfSpeech::VerbNidanHK => kami_nidan(&HA),
+        PartOfSpeech::VerbNidanKK => kami_nidan(&KA),
+        PartOfSpeech::VerbNidanMK => kami_nidan(&MA),
+        PartOfSpeech::VerbNidanRK => kami_nidan(&RA),
+        PartOfSpeech::VerbNidanTK => kami_nidan(&TA),
+        PartOfSpeech::VerbNidanYK => kami_nidan(&YA),
+        PartOfSpeech::VerbNidanAS => shimo_nidan(&A),
+        PartOfSpeech::VerbNidanBS => shimo_nidan(&BA),
+        PartOfSpeech::VerbNidanDS => shimo_nidan(&DA),
+        PartOfSpeech::VerbNidanGS => shimo_nidan(&GA),
+        PartOfSpeech::VerbNidanHS => shimo_nidan(&HA),
+        PartOfSpeech::VerbNidanKS => shimo_nidan(&KA),
+        PartOfSpeech::VerbNidanMS => shimo_nidan(&MA),
+        PartOfSpeech::VerbNidanNS => shimo_nidan(&NA),
+        PartOfSpeech::VerbNidanRS => shimo_nidan(&RA),
+        PartOfSpeech::VerbNidanSS => shimo_nidan(&SA),
+        PartOfSpeech::VerbNidanTS => shimo_nidan(&TA),
+        PartOfSpeech::VerbNidanWS => shimo_nidan(&WA),
+        PartOfSpeech::VerbNidanYS => shimo_nidan(&YA),
+        PartOfSpeech::VerbNidanZS => shimo_nidan(&ZA),
+        PartOfSpeech::VerbNu => NU,
+        PartOfSpeech::VerbRu => RI,
+        PartOfSpeech::VerbSuC => SU,
+        _ => return None,
+    };
+
+    Some(table)
+}
@@ -7,6 +7,7 @@ use musli::{Decode, Encode};
 use musli_zerocopy::ZeroCopy;
 use serde::{Deserialize, Serialize};
 
+use crate::inflection::classical;
 use crate::inflection::macros;
 use crate::inflection::Inflections;
 use crate::jmdict::Entry;
@@ -64,9 +65,13 @@ pub struct Reading {
 }
 
 /// The kind of word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum Kind {
     Verb,
     Adjective,
+    /// A noun which is conjugated by attaching する.
+    Noun,
 }
 
 /// Try to conjugate the given entry as a verb.
@@ -111,12 +116,10 @@ pub fn conjugate<'a>(entry: &Entry<'a>) -> Vec<(Reading, Inflections<'a>, Kind)>
                     });
 
                     kind = Kind::Verb;
-                    chau_stem = Some((Fragments::new([k], [r], ["っ"]), false));
+                    chau_stem = Some((Fragments::new([k], [r], []), false));
                 }
                 PartOfSpeech::VerbGodanKS => {
-                    let Some((kanji_stem, reading_prefix)) =
-                        extract_stem(kanji_text, reading_text, 'く')
-                    else {
+                    let Some((k, r)) = match_char(kanji_text, reading_text, 'く') else {
                         allowlist!();
                         continue;
                     };
@@ -125,15 +128,12 @@ pub fn conjugate<'a>(entry: &Entry<'a>) -> Vec<(Reading, Inflections<'a>, Kind)>
                         inflections.insert(
                             inflect,
                             &[],
-                            Fragments::new([kanji_stem], [reading_prefix, prefix], [suffix]),
+                            Fragments::new([k], [r], [prefix, suffix]),
                         );
                     });
 
                     kind = Kind::Verb;
-                    chau_stem = Some((
-                        Fragments::new([kanji_stem], [reading_prefix, "き"], ["っ"]),
-                        false,
-                    ));
+                    chau_stem = Some((Fragments::new([k], [r], ["っ"]), false));
                 }
                 PartOfSpeech::VerbGodanU | PartOfSpeech::VerbGodanUS => {
                     let Some((k, r)) = match_char(kanji_text, reading_text, 'う') else {
@@ -169,10 +169,45 @@ pub fn conjugate<'a>(entry: &Entry<'a>) -> Vec<(Reading, Inflections<'a>, Kind)>
                     kind = Kind::Verb;
                     chau_stem = Some((Fragments::new([k], [r], ["っ"]), false));
                 }
-                PartOfSpeech::VerbGodanR
-                | PartOfSpeech::VerbGodanRI
-                | PartOfSpeech::VerbGodanAru
-                | PartOfSpeech::VerbGodanUru => {
+                PartOfSpeech::VerbGodanAru => {
+                    let Some((k, r)) = match_char(kanji_text, reading_text, 'る') else {
+                        allowlist!();
+                        continue;
+                    };
+
+                    macros::godan_aru_base(|prefix, suffix, inflect| {
+                        inflections.insert(
+                            inflect,
+                            &[],
+                            Fragments::new([k], [r], [prefix, suffix]),
+                        );
+                    });
+
+                    kind = Kind::Verb;
+                    chau_stem = Some((Fragments::new([k], [r], ["っ"]), false));
+                }
+                PartOfSpeech::VerbGodanRI => {
+                    let Some((k, r)) = match_char(kanji_text, reading_text, 'る') else {
+                        allowlist!();
+                        continue;
+                    };
+
+                    // The plain negative of ある is ない, which replaces the
+                    // verb entirely instead of attaching to its a-stem.
+                    macros::godan_ru_base(|prefix, suffix, inflect| {
+                        let fragments = if prefix == "ら" && suffix.starts_with('な') {
+                            Fragments::new([], [], [suffix])
+                        } else {
+                            Fragments::new([k], [r], [prefix, suffix])
+                        };
+
+                        inflections.insert(inflect, &[], fragments);
+                    });
+
+                    kind = Kind::Verb;
+                    chau_stem = Some((Fragments::new([k], [r], ["っ"]), false));
+                }
+                PartOfSpeech::VerbGodanR | PartOfSpeech::VerbGodanUru => {
                     let Some((k, r)) = match_char(kanji_text, reading_text, 'る') else {
                         allowlist!();
                         continue;
@@ -292,27 +327,101 @@ pub fn conjugate<'a>(entry: &Entry<'a>) -> Vec<(Reading, Inflections<'a>, Kind)>
                     chau_stem = Some((Fragments::new([k], [r], ["し"]), false));
                 }
                 PartOfSpeech::VerbSuruSpecial | PartOfSpeech::VerbSuruIncluded => {
-                    let Some((kanji_stem, reading_prefix)) =
-                        extract_stem(kanji_text, reading_text, 'る')
-                    else {
-                        allowlist!();
-                        continue;
-                    };
+                    // Compounds such as 愛する keep their stem as-is, while
+                    // 為る has its kanji replaced by the conjugated kana.
+                    if let (Some(k), Some(r)) = (
+                        kanji_text.strip_suffix("する"),
+                        reading_text.strip_suffix("する"),
+                    ) {
+                        let special = matches!(pos, PartOfSpeech::VerbSuruSpecial);
+
+                        macros::suru_base(|prefix, suffix, inflect| {
+                            // Special class verbs form their potential with
+                            // せる, like 愛せる rather than 愛できる.
+                            let (prefix, suffix) = match suffix.strip_prefix('き') {
+                                Some(suffix) if special && prefix == "で" => ("せ", suffix),
+                                _ => (prefix, suffix),
+                            };
+
+                            inflections.insert(
+                                inflect,
+                                &[],
+                                Fragments::new([k, prefix], [r, prefix], [suffix]),
+                            );
+                        });
+
+                        chau_stem = Some((Fragments::new([k, "し"], [r, "し"], []), false));
+                    } else {
+                        let Some((kanji_stem, reading_prefix)) =
+                            extract_stem(kanji_text, reading_text, 'る')
+                        else {
+                            allowlist!();
+                            continue;
+                        };
+
+                        let kana = kanji.is_none();
+
+                        macros::suru_base(|prefix, suffix, inflect| {
+                            inflections.insert(
+                                inflect,
+                                &[],
+                                irregular(kana, kanji_stem, reading_prefix, prefix, suffix),
+                            );
+                        });
+
+                        chau_stem =
+                            Some((irregular(kana, kanji_stem, reading_prefix, "し", ""), false));
+                    }
+
+                    kind = Kind::Verb;
+                }
+                PartOfSpeech::VerbSuru => {
+                    inflections = Inflections::new(Full::new(kanji_text, reading_text, "する"));
 
                     macros::suru_base(|prefix, suffix, inflect| {
                         inflections.insert(
                             inflect,
                             &[],
-                            Fragments::new([kanji_stem], [reading_prefix, prefix], [suffix]),
+                            Fragments::new([kanji_text, prefix], [reading_text, prefix], [suffix]),
                         );
                     });
 
-                    kind = Kind::Verb;
+                    kind = Kind::Noun;
                     chau_stem = Some((
-                        Fragments::new([kanji_stem], [reading_prefix, "し"], []),
+                        Fragments::new([kanji_text, "し"], [reading_text, "し"], []),
                         false,
                     ));
                 }
+                PartOfSpeech::VerbZuru => {
+                    let (Some(k), Some(r)) = (
+                        kanji_text.strip_suffix("ずる"),
+                        reading_text.strip_suffix("ずる"),
+                    ) else {
+                        allowlist!();
+                        continue;
+                    };
+
+                    // Zuru verbs conjugate like the ichidan verb ending in
+                    // じる that they are an alternative form of.
+                    macros::ichidan_te(|suffix, inflect| {
+                        inflections.insert(
+                            inflect,
+                            &[],
+                            Fragments::new([k, "じ"], [r, "じ"], [suffix]),
+                        );
+                    });
+
+                    macros::classical(&classical::ZURU, |[base, tail], suffix, inflect| {
+                        inflections.insert(
+                            inflect,
+                            &[],
+                            Fragments::new([k], [r], [base, tail, suffix]),
+                        );
+                    });
+
+                    kind = Kind::Verb;
+                    chau_stem = Some((Fragments::new([k, "じ"], [r, "じ"], []), false));
+                }
                 PartOfSpeech::VerbKuru => {
                     let Some((kanji_stem, reading_prefix)) =
                         extract_stem(kanji_text, reading_text, 'る')
@@ -321,11 +430,13 @@ pub fn conjugate<'a>(entry: &Entry<'a>) -> Vec<(Reading, Inflections<'a>, Kind)>
                         continue;
                     };
 
+                    let kana = kanji.is_none();
+
                     macros::kuru_base(|prefix, suffix, inflect| {
                         inflections.insert(
                             inflect,
                             &[],
-                            Fragments::new([kanji_stem], [reading_prefix, prefix], [suffix]),
+                            irregular(kana, kanji_stem, reading_prefix, prefix, suffix),
                         );
                     });
 
@@ -353,11 +464,21 @@ pub fn conjugate<'a>(entry: &Entry<'a>) -> Vec<(Reading, Inflections<'a>, Kind)>
                         continue;
                     };
 
-                    macros::adjective_ii(|prefix, suffix, inflect| {
+                    // The non-past affirmative forms keep the reading of
+                    // the entry, which is either いい or よい.
+                    let present = if reading_text[reading_prefix.len()..].starts_with('よ') {
+                        "よ"
+                    } else {
+                        "い"
+                    };
+
+                    let kana = kanji.is_none();
+
+                    macros::adjective_ii(present, |prefix, suffix, inflect| {
                         inflections.insert(
                             inflect,
                             &[],
-                            Fragments::new([kanji_stem], [reading_prefix, prefix], [suffix]),
+                            irregular(kana, kanji_stem, reading_prefix, prefix, suffix),
                         );
                     });
 
@@ -376,58 +497,259 @@ pub fn conjugate<'a>(entry: &Entry<'a>) -> Vec<(Reading, Inflections<'a>, Kind)>
                     kind = Kind::Adjective;
                     chau_stem = None;
                 }
-                _ => {
-                    continue;
+                PartOfSpeech::Copular => {
+                    let mut insert = |k, r, suffix, inflect: &[Form]| {
+                        inflections.insert(inflect, &[], Fragments::new([k], [r], [suffix]));
+                    };
+
+                    if let (Some(k), Some(r)) = (
+                        kanji_text.strip_suffix('だ'),
+                        reading_text.strip_suffix('だ'),
+                    ) {
+                        macros::copula(|suffix, inflect| insert(k, r, suffix, inflect));
+                    } else if let (Some(k), Some(r)) = (
+                        kanji_text.strip_suffix("です"),
+                        reading_text.strip_suffix("です"),
+                    ) {
+                        macros::desu(|suffix, inflect| insert(k, r, suffix, inflect));
+                    } else {
+                        // Other copulas, like である, are not conjugated.
+                        continue;
+                    }
+
+                    kind = Kind::Adjective;
+                    chau_stem = None;
+                }
+                PartOfSpeech::AuxiliaryVerb => {
+                    // Other auxiliary verbs are tagged with the class they
+                    // are conjugated as.
+                    let (Some(k), Some(r)) = (
+                        kanji_text.strip_suffix("ます"),
+                        reading_text.strip_suffix("ます"),
+                    ) else {
+                        continue;
+                    };
+
+                    macros::masu(|suffix, inflect| {
+                        inflections.insert(inflect, &[], Fragments::new([k], [r], [suffix]));
+                    });
+
+                    kind = Kind::Verb;
+                    chau_stem = None;
+                }
+                pos => {
+                    let Some(bases) = classical::of(pos) else {
+                        continue;
+                    };
+
+                    let Some((k, r)) = match_char(kanji_text, reading_text, bases.ending()) else {
+                        allowlist!();
+                        continue;
+                    };
+
+                    macros::classical(&bases, |[base, tail], suffix, inflect| {
+                        inflections.insert(
+                            inflect,
+                            &[],
+                            Fragments::new([k], [r], [base, tail, suffix]),
+                        );
+                    });
+
+                    kind = Kind::Verb;
+                    chau_stem = None;
                 }
             };
 
-            if let Some(te) = inflections.get(inflect!(Te)).cloned() {
-                inflections.insert(&[TeIru, Te, Short], &[], te.concat(["る"]));
-                inflections.insert(&[TeIru, Te, Past, Short], &[], te.concat(["た"]));
+            // Constructions based on the te-form only apply to verbs.
+            let te = match kind {
+                Kind::Verb | Kind::Noun => inflections.get(inflect!(Te)).cloned(),
+                Kind::Adjective => None,
+            };
 
+            if let Some(te) = te {
                 macros::ichidan(|suffix, inflect| {
-                    inflections.insert(inflect, &[TeIru, Te], te.concat(["い", suffix]))
+                    if macros::auxiliary(inflect) {
+                        inflections.insert(inflect, &[TeIru, Te], te.concat(["い", suffix]))
+                    }
                 });
 
-                macros::godan_ru(|prefix, suffix, inflect| {
+                macros::te_aru(|prefix, suffix, inflect| {
                     inflections.insert(inflect, &[TeAru, Te], te.concat(["あ", prefix, suffix]));
                 });
 
                 macros::godan_iku(|prefix, suffix, inflect| {
-                    inflections.insert(inflect, &[TeIku, Te], te.concat(["い", prefix, suffix]));
+                    if macros::auxiliary(inflect) {
+                        inflections.insert(
+                            inflect,
+                            &[TeIku, Te],
+                            te.concat(["い", prefix, suffix]),
+                        );
+                    }
                 });
 
                 macros::godan_u(|prefix, suffix, inflect| {
-                    inflections.insert(
-                        inflect,
-                        &[TeShimau, Te],
-                        te.concat(["しま", prefix, suffix]),
-                    );
+                    if macros::auxiliary(inflect) {
+                        inflections.insert(
+                            inflect,
+                            &[TeShimau, Te],
+                            te.concat(["しま", prefix, suffix]),
+                        );
+                    }
                 });
 
                 macros::godan_ku(|prefix, suffix, inflect| {
-                    inflections.insert(inflect, &[TeOku, Te], te.concat(["お", prefix, suffix]));
+                    if macros::auxiliary(inflect) {
+                        inflections.insert(
+                            inflect,
+                            &[TeOku, Te],
+                            te.concat(["お", prefix, suffix]),
+                        );
+                    }
                 });
 
-                inflections.insert(&[Te, TeOku, Short], &[], te.concat(["く"]));
-
                 macros::kuru(|r, suffix, inflect| {
-                    inflections.insert(inflect, &[TeKuru, Te], te.concat([r, suffix]));
+                    if macros::auxiliary(inflect) {
+                        inflections.insert(inflect, &[TeKuru, Te], te.concat([r, suffix]));
+                    }
+                });
+
+                // Spoken contractions, like 食べてる, 食べてく and 食べとく.
+                macros::ichidan(|suffix, inflect| {
+                    if macros::auxiliary(inflect) {
+                        inflections.insert_colloquial(inflect, &[TeIru, Te], te.concat([suffix]))
+                    }
+                });
+
+                macros::godan_iku(|prefix, suffix, inflect| {
+                    if macros::auxiliary(inflect) {
+                        inflections.insert_colloquial(
+                            inflect,
+                            &[TeIku, Te],
+                            te.concat([prefix, suffix]),
+                        );
+                    }
                 });
+
+                let toku = match te.strip_suffix("て") {
+                    Some(stem) => Some((stem, "と")),
+                    None => te.strip_suffix("で").map(|stem| (stem, "ど")),
+                };
+
+                if let Some((stem, to)) = toku {
+                    macros::godan_ku(|prefix, suffix, inflect| {
+                        if macros::auxiliary(inflect) {
+                            inflections.insert_colloquial(
+                                inflect,
+                                &[TeOku, Te],
+                                stem.concat([to, prefix, suffix]),
+                            );
+                        }
+                    });
+                }
             }
 
-            if let Some((stem, de)) = chau_stem {
-                if de {
-                    macros::godan_u(|prefix, suffix, inflect| {
-                        inflections.insert(inflect, &[Chau], stem.concat(["じゃ", prefix, suffix]));
+            // Derived forms which behave like a word of their own are
+            // conjugated further, like the causative 食べさせる being
+            // conjugated as an ichidan verb.
+            if matches!(kind, Kind::Verb | Kind::Noun) {
+                let stem = |inflections: &Inflections<'a>, inflection, kana| {
+                    inflections.get(inflection)?.strip_suffix(kana)
+                };
+
+                if let Some(stem) = stem(&inflections, inflect!(Tai), "い") {
+                    macros::adjective_i(|suffix, inflect| {
+                        inflections.insert(inflect, &[Tai], stem.concat([suffix]));
+                    });
+                }
+
+                if let Some(stem) = stem(&inflections, inflect!(Tagaru), "る") {
+                    macros::godan_ru(|prefix, suffix, inflect| {
+                        if !inflect.contains(&Tagaru) {
+                            inflections.insert(inflect, &[Tagaru], stem.concat([prefix, suffix]));
+                        }
+                    });
+                }
+
+                // Ra-nuki potentials, like 食べれる for 食べられる.
+                if let Some(stem) = stem(&inflections, inflect!(Potential), "られる") {
+                    macros::ichidan(|suffix, inflect| {
+                        if macros::underived(inflect) {
+                            inflections.insert_colloquial(
+                                inflect,
+                                &[Potential],
+                                stem.concat(["れ", suffix]),
+                            );
+                        }
+                    });
+                }
+
+                // Negatives using ん, like 分からん for 分からない.
+                if let Some(stem) = stem(&inflections, inflect!(Nu), "ぬ") {
+                    inflections.insert_colloquial(&[Negative], &[], stem.concat(["ん"]));
+                    inflections.insert_colloquial(
+                        &[Negative, Past],
+                        &[],
+                        stem.concat(["んかった"]),
+                    );
+                }
+
+                // Applied last, so that the causative is the innermost form
+                // when the same inflection can be reached in more than one
+                // way, like 書かせたがる rather than 書きたがらせる.
+                if let Some(stem) = stem(&inflections, inflect!(Causative), "る") {
+                    macros::ichidan(|suffix, inflect| {
+                        if !inflect.contains(&Causative) {
+                            inflections.insert(inflect, &[Causative], stem.concat([suffix]));
+                        }
+                    });
+                }
+            }
+
+            // The passive doubles as a respectful form, like 書かれる, except
+            // for the aru verbs which are honorific already.
+            let passive = match pos {
+                PartOfSpeech::VerbGodanAru => None,
+                _ => inflections.get(inflect!(Passive)),
+            };
+
+            if let Some(stem) = passive.and_then(|f| f.strip_suffix("る")) {
+                macros::ichidan(|suffix, inflect| {
+                    if macros::passive_honorific(inflect) {
+                        inflections.insert(inflect, &[Passive, Honorific], stem.concat([suffix]));
+                    }
+                });
+            }
+
+            if takes_keigo_prefix(pos, reading_text) {
+                if let Some(stem) = inflections.get(inflect!(Stem)).map(|f| f.prefix("お")) {
+                    macros::godan_ru_base(|prefix, suffix, inflect| {
+                        if macros::honorific(inflect) {
+                            inflections.insert(
+                                inflect,
+                                &[Honorific],
+                                stem.concat(["にな", prefix, suffix]),
+                            );
+                        }
                     });
-                } else {
-                    macros::godan_u(|prefix, suffix, inflect| {
-                        inflections.insert(inflect, &[Chau], stem.concat(["ちゃ", prefix, suffix]));
+
+                    macros::suru_base(|prefix, suffix, inflect| {
+                        if macros::humble(inflect) {
+                            inflections.insert(inflect, &[Humble], stem.concat([prefix, suffix]));
+                        }
                     });
                 }
             }
 
+            if let Some((stem, de)) = chau_stem {
+                let chau = if de { "じゃ" } else { "ちゃ" };
+
+                macros::godan_u(|prefix, suffix, inflect| {
+                    if macros::auxiliary(inflect) {
+                        inflections.insert(inflect, &[Chau], stem.concat([chau, prefix, suffix]));
+                    }
+                });
+            }
+
             let reading = Reading {
                 kanji: kanji.map(|(i, _)| i as u8).unwrap_or(u8::MAX),
                 reading: reading.0 as u8,
@@ -440,6 +762,50 @@ pub fn conjugate<'a>(entry: &Entry<'a>) -> Vec<(Reading, Inflections<'a>, Kind)>
     output
 }
 
+/// Test if the verb forms keigo by prefixing its stem with お, like お書きになる
+/// and お書きする.
+///
+/// Irregular verbs and ichidan verbs with a single kana stem such as 見る use
+/// special verbs like ご覧になる instead, and the aru verbs are honorific
+/// already.
+fn takes_keigo_prefix(pos: PartOfSpeech, reading_text: &str) -> bool {
+    match pos {
+        PartOfSpeech::VerbIchidan | PartOfSpeech::VerbIchidanS => reading_text.chars().count() > 2,
+        PartOfSpeech::VerbGodanU
+        | PartOfSpeech::VerbGodanUS
+        | PartOfSpeech::VerbGodanT
+        | PartOfSpeech::VerbGodanR
+        | PartOfSpeech::VerbGodanRI
+        | PartOfSpeech::VerbGodanK
+        | PartOfSpeech::VerbGodanG
+        | PartOfSpeech::VerbGodanM
+        | PartOfSpeech::VerbGodanB
+        | PartOfSpeech::VerbGodanN
+        | PartOfSpeech::VerbGodanS => true,
+        _ => false,
+    }
+}
+
+/// Construct the fragments of an irregular word, where the last kana of the
+/// reading is replaced with `prefix`. Words written in kana only have no kanji
+/// stem, so the reading is used in its place.
+fn irregular<'a>(
+    kana: bool,
+    kanji_stem: &'a str,
+    reading_prefix: &'a str,
+    prefix: &'a str,
+    suffix: &'a str,
+) -> Fragments<'a> {
+    if kana {
+        Fragments::new([reading_prefix, prefix], [reading_prefix, prefix], [suffix])
+    } else {
+        Fragments::new([kanji_stem], [reading_prefix, prefix], [suffix])
+    }
+}
+
+/// Extract the stem of an irregular word ending in `c`, like 来る or いい,
+/// where the last two kana of the reading are replaced when conjugated but only
+/// the last character of the kanji is.
 fn extract_stem<'a>(
     kanji_text: &'a str,
     reading_text: &'a str,
@@ -506,6 +872,22 @@ pub(crate) fn reading_permutations<'a>(
     readings
 }
 
+/// Find the reading of the entry whose kanji or kana matches `text`.
+pub(crate) fn find_reading(entry: &Entry<'_>, text: &str) -> Option<Reading> {
+    for (kanji, reading) in reading_permutations(entry) {
+        let (_, kanji_text) = kanji.unwrap_or(reading);
+
+        if kanji_text == text || reading.1 == text {
+            return Some(Reading {
+                kanji: kanji.map(|(i, _)| i as u8).unwrap_or(u8::MAX),
+                reading: reading.0 as u8,
+            });
+        }
+    }
+
+    None
+}
+
 /// If the entry is a verb, figure out the verb kind.
 pub(crate) fn parts_of_speech(entry: &Entry<'_>) -> Set<PartOfSpeech> {
     let mut pos = Set::new();
@@ -516,5 +898,16 @@ pub(crate) fn parts_of_speech(entry: &Entry<'_>) -> Set<PartOfSpeech> {
         }
     }
 
+    // Auxiliary adjectives such as たい or らしい are conjugated as
+    // i-adjectives, even if they are not tagged as such.
+    if pos.contains(PartOfSpeech::AuxiliaryAdjective)
+        && entry
+            .reading_elements
+            .iter()
+            .all(|r| r.text.ends_with('い'))
+    {
+        pos.insert(PartOfSpeech::AdjectiveI);
+    }
+
     pos
 }
@@ -0,0 +1,885 @@
+//! Query-time deinflection, which walks an inflected surface form backwards
+//! through chains of rules towards candidate dictionary forms.
+//!
+//! The rules are derived from the same tables as the forward conjugations in
+//! [`macros`], so anything which can be conjugated can also be deinflected.
+//! Rules whose output behaves like a new word, such as the causative
+//! 食べさせる being an ichidan verb, can be chained to find forms which are
+//! never generated in the forward direction.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::OnceLock;
+
+use fixed_map::Set;
+
+use crate::inflection::classical;
+use crate::inflection::macros;
+use crate::inflection::{Form, Inflection, Kind};
+use crate::PartOfSpeech;
+
+use Form::*;
+
+/// The class of a word being deinflected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Class {
+    Ichidan,
+    GodanU,
+    GodanTsu,
+    GodanRu,
+    GodanAru,
+    GodanKu,
+    GodanIku,
+    GodanGu,
+    GodanMu,
+    GodanBu,
+    GodanNu,
+    GodanSu,
+    Suru,
+    SuruNoun,
+    Zuru,
+    Kuru,
+    AdjectiveI,
+    AdjectiveIx,
+    AdjectiveNa,
+    /// The copula だ.
+    Copula,
+    /// The polite auxiliary verb ます.
+    Masu,
+    /// A classical verb of the given part of speech.
+    Classical(PartOfSpeech),
+    /// The auxiliary ある of constructions like 書いてある, which only has
+    /// some of the forms of the verb.
+    TeAru,
+}
+
+impl Class {
+    /// Parts of speech which corresponds to this class.
+    fn parts_of_speech(self) -> Set<PartOfSpeech> {
+        let mut set = Set::new();
+
+        let pos: &[PartOfSpeech] = match self {
+            Class::Ichidan => &[PartOfSpeech::VerbIchidan, PartOfSpeech::VerbIchidanS],
+            Class::GodanU => &[PartOfSpeech::VerbGodanU, PartOfSpeech::VerbGodanUS],
+            Class::GodanTsu => &[PartOfSpeech::VerbGodanT],
+            Class::GodanRu => &[
+                PartOfSpeech::VerbGodanR,
+                PartOfSpeech::VerbGodanRI,
+                PartOfSpeech::VerbGodanUru,
+            ],
+            Class::GodanAru => &[PartOfSpeech::VerbGodanAru],
+            Class::GodanKu => &[PartOfSpeech::VerbGodanK],
+            Class::GodanIku => &[PartOfSpeech::VerbGodanKS],
+            Class::GodanGu => &[PartOfSpeech::VerbGodanG],
+            Class::GodanMu => &[PartOfSpeech::VerbGodanM],
+            Class::GodanBu => &[PartOfSpeech::VerbGodanB],
+            Class::GodanNu => &[PartOfSpeech::VerbGodanN],
+            Class::GodanSu => &[PartOfSpeech::VerbGodanS],
+            Class::Suru => &[
+                PartOfSpeech::VerbSuruSpecial,
+                PartOfSpeech::VerbSuruIncluded,
+            ],
+            Class::SuruNoun => &[PartOfSpeech::VerbSuru],
+            Class::Zuru => &[PartOfSpeech::VerbZuru],
+            Class::Kuru => &[PartOfSpeech::VerbKuru],
+            Class::AdjectiveI => &[PartOfSpeech::AdjectiveI],
+            Class::AdjectiveIx => &[PartOfSpeech::AdjectiveIx],
+            Class::AdjectiveNa => &[PartOfSpeech::AdjectiveNa],
+            Class::Copula => &[PartOfSpeech::Copular],
+            Class::Masu => &[PartOfSpeech::AuxiliaryVerb],
+            Class::Classical(pos) => {
+                set.insert(pos);
+                return set;
+            }
+            Class::TeAru => &[],
+        };
+
+        for &p in pos {
+            set.insert(p);
+        }
+
+        set
+    }
+
+    fn kind(self) -> Kind {
+        match self {
+            Class::AdjectiveI | Class::AdjectiveIx | Class::AdjectiveNa | Class::Copula => {
+                Kind::Adjective
+            }
+            Class::SuruNoun => Kind::Noun,
+            _ => Kind::Verb,
+        }
+    }
+}
+
+/// A single deinflection rule.
+struct Rule {
+    /// Prefix of the inflected word which is removed, like the お of keigo.
+    prefix: &'static str,
+    /// The inflected suffix.
+    from: String,
+    /// The dictionary suffix it is replaced with.
+    to: &'static str,
+    /// The class of the word produced by the rule.
+    output: Class,
+    /// The class of the inflected word, if it can itself be inflected further.
+    input: Option<Class>,
+    /// The inflection being removed.
+    inflection: Inflection,
+    /// If the inflected suffix is a colloquial contraction.
+    colloquial: bool,
+    /// Test if the forms removed before this rule can be applied to the
+    /// inflected word, which for example excludes the passive of 食べている.
+    accepts: fn(&[Form]) -> bool,
+}
+
+/// A candidate dictionary form produced by [`deinflect`].
+#[derive(Debug, Clone)]
+pub struct Deinflection {
+    /// The candidate dictionary form.
+    pub text: String,
+    /// Parts of speech an entry must have for the candidate to apply to it.
+    pub pos: Set<PartOfSpeech>,
+    /// The kind of word being deinflected.
+    pub kind: Kind,
+    /// The inflection which was removed to reach the candidate.
+    pub inflection: Inflection,
+    /// If any of the removed suffixes was a colloquial contraction, like
+    /// 食べてる.
+    pub colloquial: bool,
+}
+
+/// Deinflect the given input, returning every candidate dictionary form it
+/// could have been inflected from.
+///
+/// Candidates are not verified, so the caller is expected to check them
+/// against the dictionary.
+pub fn deinflect(input: &str) -> Vec<Deinflection> {
+    let rules = rules();
+
+    let mut output = Vec::new();
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    queue.push_back((input.to_owned(), None, Inflection::default(), false));
+
+    while let Some((text, class, inflection, colloquial)) = queue.pop_front() {
+        let removed = inflection.iter().collect::<Vec<_>>();
+
+        for rule in rules.matching(&text) {
+            if class.is_some() && rule.input != class {
+                continue;
+            }
+
+            if !(rule.accepts)(&removed) {
+                continue;
+            }
+
+            let Some(stem) = text.strip_suffix(rule.from.as_str()) else {
+                continue;
+            };
+
+            let Some(stem) = stem.strip_prefix(rule.prefix) else {
+                continue;
+            };
+
+            let next = inflection | rule.inflection;
+
+            // Only allow rules which contribute something new, which also
+            // guarantees that deinflection terminates.
+            if next == inflection {
+                continue;
+            }
+
+            let candidate = format!("{stem}{}", rule.to);
+
+            if candidate.is_empty() || !seen.insert((candidate.clone(), rule.output, next)) {
+                continue;
+            }
+
+            let colloquial = colloquial || rule.colloquial;
+
+            output.push(Deinflection {
+                text: candidate.clone(),
+                pos: rule.output.parts_of_speech(),
+                kind: rule.output.kind(),
+                inflection: next,
+                colloquial,
+            });
+
+            queue.push_back((candidate, Some(rule.output), next, colloquial));
+        }
+    }
+
+    output
+}
+
+/// Rules indexed by the last character of the suffix they remove, so that
+/// only the rules which could apply to a word are tested.
+struct Rules {
+    rules: Vec<Rule>,
+    /// Rules by the last character of their suffix, including the rules with
+    /// an empty suffix in their original order.
+    by_last: HashMap<char, Vec<usize>>,
+    /// Rules with an empty suffix.
+    empty: Vec<usize>,
+    /// Every character which appears in a suffix.
+    chars: HashSet<char>,
+    /// Distinct prefixes which are removed by rules.
+    prefixes: Vec<&'static str>,
+}
+
+impl Rules {
+    fn new(rules: Vec<Rule>) -> Self {
+        let mut by_last = HashMap::<_, Vec<_>>::new();
+        let mut empty = Vec::new();
+        let mut chars = HashSet::new();
+        let mut prefixes = Vec::new();
+
+        for (index, rule) in rules.iter().enumerate() {
+            match rule.from.chars().next_back() {
+                Some(c) => by_last.entry(c).or_default().push(index),
+                None => empty.push(index),
+            }
+
+            chars.extend(rule.from.chars());
+
+            if !rule.prefix.is_empty() && !prefixes.contains(&rule.prefix) {
+                prefixes.push(rule.prefix);
+            }
+        }
+
+        for indexes in by_last.values_mut() {
+            indexes.extend_from_slice(&empty);
+            indexes.sort();
+        }
+
+        Self {
+            rules,
+            by_last,
+            empty,
+            chars,
+            prefixes,
+        }
+    }
+
+    /// Rules whose suffix might match the end of the given text.
+    fn matching(&self, text: &str) -> impl Iterator<Item = &Rule> + '_ {
+        let indexes = text
+            .chars()
+            .next_back()
+            .and_then(|c| self.by_last.get(&c))
+            .unwrap_or(&self.empty);
+
+        indexes.iter().map(|&index| &self.rules[index])
+    }
+}
+
+/// Get the texts which a dictionary form has to start with for a word
+/// starting with `text` to deinflect to it.
+///
+/// Returns `None` if the end of `text` might be part of an inflected suffix,
+/// in which case the word could deinflect to nearly anything.
+pub(crate) fn stems(text: &str) -> Option<Vec<&str>> {
+    let rules = rules();
+
+    let last = text.chars().next_back()?;
+
+    if rules.chars.contains(&last) {
+        return None;
+    }
+
+    let mut stems = vec![text];
+
+    for prefix in &rules.prefixes {
+        if let Some(stem) = text.strip_prefix(prefix) {
+            stems.push(stem);
+        }
+    }
+
+    Some(stems)
+}
+
+fn rules() -> &'static Rules {
+    static RULES: OnceLock<Rules> = OnceLock::new();
+    RULES.get_or_init(|| Rules::new(build()))
+}
+
+/// The class of an inflected word, if it behaves like a word which can be
+/// inflected further.
+fn chain(forms: &[Form]) -> Option<Class> {
+    match forms {
+        [Causative] | [Passive] | [Potential] => Some(Class::Ichidan),
+        [Causative, Passive] | [Causative, Passive, Short] | [Sugiru] => Some(Class::Ichidan),
+        [Tai] | [Negative] | [Yasui] | [Nikui] => Some(Class::AdjectiveI),
+        [Sou] => Some(Class::AdjectiveNa),
+        [Tagaru] => Some(Class::GodanRu),
+        _ => None,
+    }
+}
+
+/// Accept any forms being removed before a rule.
+fn any(_: &[Form]) -> bool {
+    true
+}
+
+fn inflection(forms: &[Form]) -> Inflection {
+    let mut set = Set::new();
+
+    for &f in forms {
+        set.insert(f);
+    }
+
+    Inflection::new(set)
+}
+
+fn build() -> Vec<Rule> {
+    let mut rules = Vec::new();
+
+    let mut push = |from: String, to: &'static str, output: Class, forms: &[Form]| {
+        if forms.is_empty() {
+            return;
+        }
+
+        let input = if forms == [Te] { None } else { chain(forms) };
+
+        // The te-form acts as a base for a number of constructions, which
+        // are spliced in directly to get the same inflections as the
+        // forward conjugation.
+        if forms == [Te] {
+            if let Some(stem) = from.strip_suffix(['て', 'で']) {
+                let (te, chau) = if from.ends_with('て') {
+                    ("て", "ちゃ")
+                } else {
+                    ("で", "じゃ")
+                };
+
+                let te_forms: [(&str, Form, Class); 6] = [
+                    ("いる", TeIru, Class::Ichidan),
+                    ("ある", TeAru, Class::TeAru),
+                    ("いく", TeIku, Class::GodanIku),
+                    ("しまう", TeShimau, Class::GodanU),
+                    ("おく", TeOku, Class::GodanKu),
+                    ("くる", TeKuru, Class::Kuru),
+                ];
+
+                for (suffix, form, input) in te_forms {
+                    rules.push(Rule {
+                        prefix: "",
+                        from: format!("{stem}{te}{suffix}"),
+                        to,
+                        output,
+                        input: Some(input),
+                        inflection: inflection(&[form, Te]),
+                        colloquial: false,
+                        accepts: macros::auxiliary,
+                    });
+                }
+
+                // Spoken contractions, like 食べてる, 食べてく and 食べとく.
+                let toku = if te == "て" { "とく" } else { "どく" };
+
+                let contractions: [(&str, &str, Form, Class); 3] = [
+                    (te, "る", TeIru, Class::Ichidan),
+                    (te, "く", TeIku, Class::GodanIku),
+                    ("", toku, TeOku, Class::GodanKu),
+                ];
+
+                for (te, suffix, form, input) in contractions {
+                    rules.push(Rule {
+                        prefix: "",
+                        from: format!("{stem}{te}{suffix}"),
+                        to,
+                        output,
+                        input: Some(input),
+                        inflection: inflection(&[form, Te]),
+                        colloquial: true,
+                        accepts: macros::auxiliary,
+                    });
+                }
+
+                if output != Class::Kuru {
+                    rules.push(Rule {
+                        prefix: "",
+                        from: format!("{stem}{chau}う"),
+                        to,
+                        output,
+                        input: Some(Class::GodanU),
+                        inflection: inflection(&[Chau]),
+                        colloquial: false,
+                        accepts: macros::auxiliary,
+                    });
+                }
+            }
+        }
+
+        // Negatives using ん, like 分からん, are formed from the same base as
+        // ぬ.
+        if forms == [Nu] {
+            if let Some(stem) = from.strip_suffix('ぬ') {
+                let contractions: [(&str, &[Form]); 2] =
+                    [("ん", &[Negative]), ("んかった", &[Negative, Past])];
+
+                for (suffix, forms) in contractions {
+                    rules.push(Rule {
+                        prefix: "",
+                        from: format!("{stem}{suffix}"),
+                        to,
+                        output,
+                        input: None,
+                        inflection: inflection(forms),
+                        colloquial: true,
+                        accepts: any,
+                    });
+                }
+            }
+        }
+
+        rules.push(Rule {
+            prefix: "",
+            from,
+            to,
+            output,
+            input,
+            inflection: inflection(forms),
+            colloquial: false,
+            accepts: any,
+        });
+    };
+
+    macros::ichidan_te(|suffix, forms| {
+        push(suffix.to_owned(), "る", Class::Ichidan, forms);
+    });
+
+    type Godan = fn(&mut dyn FnMut(&'static str, &'static str, &[Form]));
+
+    let godan: [(Class, &'static str, Godan); 11] = [
+        (Class::GodanU, "う", |r| macros::godan_u_base(r)),
+        (Class::GodanTsu, "つ", |r| macros::godan_tsu_base(r)),
+        (Class::GodanRu, "る", |r| macros::godan_ru_base(r)),
+        (Class::GodanAru, "る", |r| macros::godan_aru_base(r)),
+        (Class::GodanKu, "く", |r| macros::godan_ku_base(r)),
+        (Class::GodanIku, "く", |r| macros::godan_iku_base(r)),
+        (Class::GodanGu, "ぐ", |r| macros::godan_gu_base(r)),
+        (Class::GodanMu, "む", |r| macros::godan_mu_base(r)),
+        (Class::GodanBu, "ぶ", |r| macros::godan_bu_base(r)),
+        (Class::GodanNu, "ぬ", |r| macros::godan_nu_base(r)),
+        (Class::GodanSu, "す", |r| macros::godan_su_base(r)),
+    ];
+
+    for (class, to, conjugate) in godan {
+        conjugate(&mut |prefix, suffix, forms| {
+            push(format!("{prefix}{suffix}"), to, class, forms);
+        });
+    }
+
+    macros::te_aru(|prefix, suffix, forms| {
+        push(format!("{prefix}{suffix}"), "る", Class::TeAru, forms);
+    });
+
+    macros::suru_base(|prefix, suffix, forms| {
+        push(format!("{prefix}{suffix}"), "する", Class::Suru, forms);
+        push(format!("{prefix}{suffix}"), "", Class::SuruNoun, forms);
+    });
+
+    macros::ichidan_te(|suffix, forms| {
+        push(format!("じ{suffix}"), "ずる", Class::Zuru, forms);
+    });
+
+    macros::classical(&classical::ZURU, |[base, tail], suffix, forms| {
+        push(format!("{base}{tail}{suffix}"), "ずる", Class::Zuru, forms);
+    });
+
+    for &pos in PartOfSpeech::VALUES {
+        let Some(bases) = classical::of(pos) else {
+            continue;
+        };
+
+        let [to, _] = bases.shuushi;
+
+        macros::classical(&bases, |[base, tail], suffix, forms| {
+            push(
+                format!("{base}{tail}{suffix}"),
+                to,
+                Class::Classical(pos),
+                forms,
+            );
+        });
+    }
+
+    macros::kuru_base(|prefix, suffix, forms| {
+        push(format!("{prefix}{suffix}"), "くる", Class::Kuru, forms);
+        push(format!("来{suffix}"), "来る", Class::Kuru, forms);
+    });
+
+    macros::adjective_i(|suffix, forms| {
+        push(suffix.to_owned(), "い", Class::AdjectiveI, forms);
+    });
+
+    for (present, to) in [("い", "いい"), ("よ", "よい")] {
+        macros::adjective_ii(present, |prefix, suffix, forms| {
+            push(format!("{prefix}{suffix}"), to, Class::AdjectiveIx, forms);
+        });
+    }
+
+    macros::adjective_ii("い", |_, suffix, forms| {
+        push(format!("良{suffix}"), "良い", Class::AdjectiveIx, forms);
+    });
+
+    macros::adjective_na(|suffix, forms| {
+        push(suffix.to_owned(), "", Class::AdjectiveNa, forms);
+    });
+
+    macros::copula(|suffix, forms| {
+        push(suffix.to_owned(), "だ", Class::Copula, forms);
+    });
+
+    macros::desu(|suffix, forms| {
+        push(suffix.to_owned(), "です", Class::Copula, forms);
+    });
+
+    macros::masu(|suffix, forms| {
+        push(suffix.to_owned(), "ます", Class::Masu, forms);
+    });
+
+    // Ra-nuki potentials, like 食べれる and 来れる.
+    let ra_nuki: [(&str, &str, Class); 3] = [
+        ("", "る", Class::Ichidan),
+        ("こ", "くる", Class::Kuru),
+        ("来", "来る", Class::Kuru),
+    ];
+
+    for (stem, to, output) in ra_nuki {
+        macros::ichidan(|suffix, forms| {
+            if macros::underived(forms) {
+                rules.push(Rule {
+                    prefix: "",
+                    from: format!("{stem}れ{suffix}"),
+                    to,
+                    output,
+                    input: forms.is_empty().then_some(Class::Ichidan),
+                    inflection: inflection(forms) | inflection(&[Potential]),
+                    colloquial: true,
+                    accepts: any,
+                });
+            }
+        });
+    }
+
+    // Keigo is formed by prefixing the stem of a verb with お, like
+    // お書きになる and お書きする.
+    let mut stems = vec![(Class::Ichidan, "", "る")];
+
+    for (class, to, conjugate) in godan {
+        if matches!(class, Class::GodanAru | Class::GodanIku) {
+            continue;
+        }
+
+        conjugate(&mut |_, suffix, forms| {
+            if forms == [Stem] {
+                stems.push((class, suffix, to));
+            }
+        });
+    }
+
+    for (output, stem, to) in stems {
+        let mut keigo = |from: String, forms: &[Form], form: Form, class: Class| {
+            let accepts: fn(&[Form]) -> bool = match form {
+                Honorific => macros::honorific,
+                _ => macros::humble,
+            };
+
+            if !accepts(forms) {
+                return;
+            }
+
+            rules.push(Rule {
+                prefix: "お",
+                from,
+                to,
+                output,
+                input: forms.is_empty().then_some(class),
+                inflection: inflection(forms) | inflection(&[form]),
+                colloquial: false,
+                accepts,
+            });
+        };
+
+        macros::godan_ru_base(|prefix, suffix, forms| {
+            keigo(
+                format!("{stem}にな{prefix}{suffix}"),
+                forms,
+                Honorific,
+                Class::GodanRu,
+            );
+        });
+
+        macros::suru_base(|prefix, suffix, forms| {
+            keigo(
+                format!("{stem}{prefix}{suffix}"),
+                forms,
+                Humble,
+                Class::Suru,
+            );
+        });
+    }
+
+    rules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(input: &str, text: &str, inflection: Inflection) -> bool {
+        deinflect(input)
+            .iter()
+            .any(|d| d.text == text && d.inflection == inflection)
+    }
+
+    fn colloquial(input: &str, text: &str, inflection: Inflection) -> bool {
+        deinflect(input)
+            .iter()
+            .any(|d| d.text == text && d.inflection == inflection && d.colloquial)
+    }
+
+    #[test]
+    fn test_chained() {
+        assert!(find(
+            "食べさせられなかった",
+            "食べる",
+            inflect!(Causative, Passive, Negative, Past)
+        ));
+
+        assert!(find(
+            "書かせたくない",
+            "書く",
+            inflect!(Causative, Tai, Negative)
+        ));
+
+        assert!(find(
+            "食べたくなかった",
+            "食べる",
+            inflect!(Tai, Negative, Past)
+        ));
+
+        assert!(find(
+            "行かせたがっている",
+            "行く",
+            inflect!(Causative, Tagaru, Te, TeIru)
+        ));
+    }
+
+    #[test]
+    fn test_keigo() {
+        assert!(find(
+            "お書きになりました",
+            "書く",
+            inflect!(Honorific, Polite, Past)
+        ));
+        assert!(find("お待ちします", "待つ", inflect!(Humble, Polite)));
+        assert!(find("お調べになる", "調べる", inflect!(Honorific)));
+        assert!(find(
+            "お書きになってください",
+            "書く",
+            inflect!(Honorific, Command, Polite, Kudasai)
+        ));
+        assert!(find(
+            "お書きになっている",
+            "書く",
+            inflect!(Honorific, Te, TeIru)
+        ));
+        assert!(find(
+            "お待ちしましょう",
+            "待つ",
+            inflect!(Humble, Volitional, Polite)
+        ));
+
+        // Honorifics aren't used to refer to yourself, and humble forms can't
+        // be used as commands.
+        assert!(!find("お書きになれ", "書く", inflect!(Honorific, Command)));
+        assert!(!find(
+            "お書きになろう",
+            "書く",
+            inflect!(Honorific, Volitional)
+        ));
+        assert!(!find(
+            "お待ちしなさい",
+            "待つ",
+            inflect!(Humble, Command, Polite, Nasai)
+        ));
+        assert!(find(
+            "いらっしゃいませ",
+            "いらっしゃる",
+            inflect!(Polite, Command)
+        ));
+        assert!(find("いらっしゃい", "いらっしゃる", inflect!(Command)));
+        assert!(find("なさいました", "なさる", inflect!(Polite, Past)));
+    }
+
+    #[test]
+    fn test_colloquial() {
+        assert!(colloquial("食べてる", "食べる", inflect!(Te, TeIru)));
+        assert!(colloquial("読んどく", "読む", inflect!(Te, TeOku)));
+        assert!(colloquial("持ってく", "持つ", inflect!(Te, TeIku)));
+        assert!(colloquial("食べれる", "食べる", inflect!(Potential)));
+        assert!(colloquial("来れる", "来る", inflect!(Potential)));
+        assert!(colloquial("分からん", "分かる", inflect!(Negative)));
+        assert!(colloquial(
+            "食べんかった",
+            "食べる",
+            inflect!(Negative, Past)
+        ));
+        assert!(!colloquial("食べている", "食べる", inflect!(Te, TeIru)));
+    }
+
+    #[test]
+    fn test_auxiliary() {
+        assert!(find("ました", "ます", inflect!(Past)));
+        assert!(find("ませんでした", "ます", inflect!(Negative, Past)));
+        assert!(find("だった", "だ", inflect!(Past)));
+        assert!(find("ではなかった", "だ", inflect!(Negative, Past)));
+        assert!(find("らしくない", "らしい", inflect!(Negative)));
+    }
+
+    #[test]
+    fn test_te() {
+        assert!(find("書いている", "書く", inflect!(Te, TeIru)));
+        assert!(find(
+            "読んでいなかった",
+            "読む",
+            inflect!(Te, TeIru, Negative, Past)
+        ));
+        assert!(find("買っちゃった", "買う", inflect!(Chau, Past)));
+        assert!(find(
+            "書いてありました",
+            "書く",
+            inflect!(Te, TeAru, Polite, Past)
+        ));
+        assert!(find(
+            "待っていられる",
+            "待つ",
+            inflect!(Te, TeIru, Potential)
+        ));
+    }
+
+    #[test]
+    fn test_te_derived() {
+        // Derived forms are formed from the main verb before the
+        // te-construction is applied.
+        assert!(find(
+            "食べさせている",
+            "食べる",
+            inflect!(Causative, Te, TeIru)
+        ));
+        assert!(find(
+            "書かれてしまった",
+            "書く",
+            inflect!(Passive, Te, TeShimau, Past)
+        ));
+
+        assert!(find("飲みすぎている", "飲む", inflect!(Sugiru, Te, TeIru)));
+        assert!(!find("飲んでいすぎる", "飲む", inflect!(Te, TeIru, Sugiru)));
+
+        assert!(!find(
+            "勉強していさせる",
+            "勉強",
+            inflect!(Te, TeIru, Causative)
+        ));
+        assert!(!find(
+            "食べてあられる",
+            "食べる",
+            inflect!(Te, TeAru, Passive)
+        ));
+        assert!(!find("食べちゃわれる", "食べる", inflect!(Chau, Passive)));
+        assert!(!find("勉強してあらぬ", "勉強", inflect!(Te, TeAru, Nu)));
+    }
+
+    #[test]
+    fn test_forms() {
+        assert!(find(
+            "食べさせられた",
+            "食べる",
+            inflect!(Causative, Passive, Past)
+        ));
+        assert!(find(
+            "書かされなかった",
+            "書く",
+            inflect!(Causative, Passive, Past, Negative, Short)
+        ));
+        assert!(find("読みなさい", "読む", inflect!(Command, Polite, Nasai)));
+        assert!(find("降りそうだった", "降りる", inflect!(Sou, Past)));
+        assert!(find("言わずに", "言う", inflect!(ZuNi)));
+        assert!(find("知らぬ", "知る", inflect!(Nu)));
+        assert!(find("歩きながら", "歩く", inflect!(Nagara)));
+        assert!(find("泳いだり", "泳ぐ", inflect!(Tari)));
+        assert!(find("飲みすぎた", "飲む", inflect!(Sugiru, Past)));
+        assert!(find("読みにくくない", "読む", inflect!(Nikui, Negative)));
+        assert!(find(
+            "行かなくちゃ",
+            "行く",
+            inflect!(Hypothetical, Negative, Kucha)
+        ));
+        assert!(find("勉強しやすい", "勉強する", inflect!(Yasui)));
+        assert!(find("来ず", "来る", inflect!(Zu)));
+    }
+
+    #[test]
+    fn test_adjectives() {
+        assert!(find("高くなかった", "高い", inflect!(Negative, Past)));
+        assert!(find("高ければ", "高い", inflect!(Hypothetical)));
+        assert!(find("高かろう", "高い", inflect!(Volitional)));
+        assert!(find("高さ", "高い", inflect!(Sa)));
+        assert!(find("高すぎた", "高い", inflect!(Sugiru, Past)));
+        assert!(find("高そうだった", "高い", inflect!(Sou, Past)));
+        assert!(find("よさそう", "いい", inflect!(Sou)));
+        assert!(find("よいです", "よい", inflect!(Polite)));
+        assert!(find("良くて", "良い", inflect!(Te)));
+        assert!(find("静かじゃない", "静か", inflect!(Negative, Short)));
+        assert!(find("静かなら", "静か", inflect!(Hypothetical)));
+        assert!(find("静かな", "静か", inflect!(Attributive)));
+        assert!(find("静かに", "静か", inflect!(Adverb)));
+    }
+
+    #[test]
+    fn test_irregular() {
+        assert!(find("来なかった", "来る", inflect!(Negative, Past)));
+        assert!(find("こられる", "くる", inflect!(Passive)));
+        assert!(find(
+            "しませんでした",
+            "する",
+            inflect!(Negative, Past, Polite)
+        ));
+        assert!(find("よくない", "いい", inflect!(Negative)));
+        assert!(find("でした", "だ", inflect!(Past, Polite)));
+        assert!(find("でした", "です", inflect!(Past)));
+    }
+
+    #[test]
+    fn test_classical() {
+        assert!(find("信ぜず", "信ずる", inflect!(Zu, Classical)));
+        assert!(find("信ずべし", "信ずる", inflect!(Beshi, Classical)));
+        assert!(find("死ぬる", "死ぬ", inflect!(Attributive, Classical)));
+        assert!(find("起くれば", "起く", inflect!(Conditional, Classical)));
+        assert!(find("言はむ", "言ふ", inflect!(Volitional, Classical)));
+        assert!(find("あるべし", "あり", inflect!(Beshi, Classical)));
+        assert!(find("為せ", "為す", inflect!(Command, Classical)));
+    }
+
+    #[test]
+    fn test_suru() {
+        assert!(find("勉強した", "勉強", inflect!(Past)));
+        assert!(find("運転させられる", "運転", inflect!(Causative, Passive)));
+        assert!(find("勉強している", "勉強", inflect!(Te, TeIru)));
+        assert!(find("案じない", "案ずる", inflect!(Negative)));
+        assert!(find("案じた", "案ずる", inflect!(Past)));
+    }
+
+    #[test]
+    fn test_stems() {
+        assert_eq!(stems("日本語"), Some(vec!["日本語"]));
+        assert_eq!(stems("お茶"), Some(vec!["お茶", "茶"]));
+        // Could be the start of 食べる or 食べられない.
+        assert_eq!(stems("食べ"), None);
+        assert_eq!(stems("来"), None);
+    }
+}
@@ -0,0 +1,217 @@
+//! Human-readable explanations of inflections.
+//!
+//! An [`Inflection`] is an unordered set of forms, so this puts them in the
+//! order they are applied to the dictionary form of a word. For example the
+//! forms of 食べさせられなかった are explained as causative → passive →
+//! negative → past.
+//!
+//! The order follows how words are conjugated. Forms like the causative are
+//! derived from the main verb before any te-construction, like 食べさせている,
+//! while others like the potential are formed from the auxiliary, like
+//! 食べていられる. This means that 書けている is explained like 書いていられる,
+//! since the two can't be told apart.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::inflection::{Form, Inflection, Kind};
+
+use Form::*;
+
+/// Forms which modify the main verb into a new word which is inflected
+/// further, including through te-constructions.
+const DERIVED: [Form; 4] = [Causative, Passive, Tagaru, Sugiru];
+
+/// Keigo, which is applied to the derived word.
+const KEIGO: [Form; 2] = [Honorific, Humble];
+
+/// Constructions which are based on the te-form.
+const TE: [Form; 6] = [TeIru, TeAru, TeIku, TeShimau, TeOku, TeKuru];
+
+/// Forms which modify the word into a new word after any te-construction,
+/// like the potential of 食べていられる.
+const OUTER: [Form; 6] = [Potential, Tai, Yasui, Nikui, Sou, Sa];
+
+/// Forms which are applied after the derived forms and te-constructions,
+/// other than te itself.
+const TAIL: [Form; 24] = [
+    Chau,
+    Polite,
+    Negative,
+    Past,
+    Conditional,
+    Hypothetical,
+    Kya,
+    Kucha,
+    Volitional,
+    Darou,
+    Beshi,
+    Command,
+    Kudasai,
+    Nasai,
+    Yo,
+    Zu,
+    ZuNi,
+    Nu,
+    Nagara,
+    Tari,
+    Adverb,
+    Attributive,
+    Conversation,
+    Short,
+];
+
+/// A single step in an [`Explanation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Step {
+    /// The form applied in this step.
+    pub form: Form,
+    /// Short name of the form, see [`Form::describe`].
+    pub name: &'static str,
+    /// Gloss of the form, see [`Form::title`].
+    pub gloss: &'static str,
+}
+
+impl Step {
+    fn new(form: Form) -> Self {
+        Self {
+            form,
+            name: form.describe(),
+            gloss: form.title(),
+        }
+    }
+}
+
+/// An explanation of an inflection, as an ordered chain of steps leading
+/// from the dictionary form to the inflected word.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Explanation {
+    /// The kind of word which was inflected.
+    pub kind: Kind,
+    /// Steps in the order they are applied.
+    pub steps: Vec<Step>,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut it = self.steps.iter();
+
+        if let Some(step) = it.next() {
+            f.write_str(step.name)?;
+        }
+
+        for step in it {
+            write!(f, " → {}", step.name)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Explain the given inflection of a word of the given kind.
+pub fn explain(inflection: Inflection, kind: Kind) -> Explanation {
+    let mut steps = Vec::new();
+
+    if inflection.contains(Classical) {
+        steps.push(Step::new(Classical));
+    }
+
+    if inflection.contains(Stem) {
+        steps.push(Step::new(Stem));
+    }
+
+    for form in DERIVED {
+        if inflection.contains(form) {
+            steps.push(Step::new(form));
+        }
+    }
+
+    for form in KEIGO {
+        if inflection.contains(form) {
+            steps.push(Step::new(form));
+        }
+    }
+
+    // The te-form comes first if it is the base of a construction, and
+    // otherwise last, like in 食べなくて.
+    let te_base = TE.iter().any(|&form| inflection.contains(form));
+
+    if te_base && inflection.contains(Te) {
+        steps.push(Step::new(Te));
+    }
+
+    for form in TE {
+        if inflection.contains(form) {
+            steps.push(Step::new(form));
+        }
+    }
+
+    for form in OUTER {
+        if inflection.contains(form) {
+            steps.push(Step::new(form));
+        }
+    }
+
+    for form in TAIL {
+        if form == Conversation && !te_base && inflection.contains(Te) {
+            steps.push(Step::new(Te));
+        }
+
+        if inflection.contains(form) {
+            steps.push(Step::new(form));
+        }
+    }
+
+    Explanation { kind, steps }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain() {
+        let explain = |inflection, kind| explain(inflection, kind).to_string();
+
+        assert_eq!(
+            explain(inflect!(Negative, Past, Passive, Causative), Kind::Verb),
+            "causative → passive → negative → past"
+        );
+        assert_eq!(
+            explain(inflect!(Te, TeIru, Past, Polite), Kind::Verb),
+            "~te → ~te iru → polite → past"
+        );
+        assert_eq!(
+            explain(inflect!(Te, Negative), Kind::Adjective),
+            "negative → ~te"
+        );
+        assert_eq!(
+            explain(inflect!(Negative, Short), Kind::Adjective),
+            "negative → short"
+        );
+        assert_eq!(
+            explain(inflect!(Honorific, Polite, Past), Kind::Verb),
+            "honorific → polite → past"
+        );
+        assert_eq!(
+            explain(inflect!(Causative, Te, TeIru), Kind::Verb),
+            "causative → ~te → ~te iru"
+        );
+        assert_eq!(
+            explain(inflect!(Te, TeIru, Potential, Negative), Kind::Verb),
+            "~te → ~te iru → potential → negative"
+        );
+        assert_eq!(
+            explain(inflect!(Honorific, Tai), Kind::Verb),
+            "honorific → ~tai"
+        );
+        assert_eq!(explain(Inflection::default(), Kind::Verb), "");
+    }
+
+    #[test]
+    fn test_explain_all() {
+        let explanation = explain(Inflection::all(), Kind::Verb);
+        assert_eq!(explanation.steps.len(), Form::ALL.len());
+    }
+}
@@ -13,6 +13,12 @@ pub(crate) struct Godan {
     pub(super) past_conditional: &'static str,
     // If で verb, else て.
     pub(super) de: bool,
+    /// Kana which ます attaches to, which is the same as `i` except for the
+    /// honorific aru verbs.
+    pub(super) polite: &'static str,
+    /// Kana of the command form, which is the same as `e` except for the
+    /// honorific aru verbs.
+    pub(super) command: &'static str,
 }
 
 impl Godan {
@@ -39,6 +45,17 @@ impl Godan {
             past,
             past_conditional,
             de,
+            polite: i,
+            command: e,
+        }
+    }
+
+    /// Use the given kana for the polite and command forms.
+    const fn with_polite(self, polite: &'static str, command: &'static str) -> Self {
+        Self {
+            polite,
+            command,
+            ..self
         }
     }
 }
@@ -82,6 +99,21 @@ pub(super) static RU: &Godan = &Godan::new(
     "ったら",
     false,
 );
+/// The godan table for honorific aru verbs such as いらっしゃる, which use い
+/// for the polite and command forms.
+pub(super) static ARU: &Godan = &Godan::new(
+    "ら",
+    "り",
+    "る",
+    "れ",
+    "ろ",
+    "って",
+    "っ",
+    "った",
+    "ったら",
+    false,
+)
+.with_polite("い", "い");
 /// The KU godan table.
 pub(super) static KU: &Godan = &Godan::new(
     "か",
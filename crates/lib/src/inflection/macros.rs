@@ -1,5 +1,6 @@
 //! Macros to construct conjugations.
 
+use crate::inflection::classical::{Base, Bases};
 use crate::inflection::godan::{self, Godan};
 use crate::inflection::Form;
 
@@ -16,19 +17,23 @@ pub fn ichidan(mut r: impl FnMut(&'static str, &[Form])) {
     r("なかった", &[Past, Negative]);
     r("ませんでした", &[Past, Negative, Polite]);
     r("ろ", &[Command]);
-    r("なさい", &[Command, Polite]);
+    r("なさい", &[Command, Polite, Nasai]);
     r("てください", &[Command, Polite, Kudasai]);
     r("よ", &[Command, Yo]);
     r("るな", &[Command, Negative]);
     r("ないでください", &[Command, Negative, Polite]);
-    r("ば", &[Hypothetical]);
+    r("れば", &[Hypothetical]);
     r("なければ", &[Hypothetical, Negative]);
     r("なきゃ", &[Hypothetical, Negative, Kya]);
+    r("なくちゃ", &[Hypothetical, Negative, Kucha]);
+    r("なけりゃ", &[Hypothetical, Negative, Short]);
     r("たら", &[Conditional]);
     r("ましたら", &[Conditional, Polite]);
     r("なかったら", &[Conditional, Negative]);
     r("ませんでしたら", &[Conditional, Negative, Polite]);
-    r("れる", &[Passive, Conversation]);
+    // Ra-nuki forms like 食べれる are potentials rather than conversational
+    // passives, so they are produced as colloquial potentials in the
+    // conjugator and the deinflector instead of from a row here.
     r("られる", &[Passive]);
     r("られます", &[Passive, Polite]);
     r("られない", &[Passive, Negative]);
@@ -50,10 +55,27 @@ pub fn ichidan(mut r: impl FnMut(&'static str, &[Form])) {
     r("ないだろう", &[Volitional, Negative]);
     r("ないでしょう", &[Volitional, Negative, Polite]);
     r("させる", &[Causative]);
+    r("させられる", &[Causative, Passive]);
+    r("させられます", &[Causative, Passive, Polite]);
+    r("させられない", &[Causative, Passive, Negative]);
+    r("させられた", &[Causative, Passive, Past]);
+    r("させられなかった", &[Causative, Passive, Past, Negative]);
     r("たい", &[Tai]);
     r("たくない", &[Tai, Negative]);
     r("たかった", &[Tai, Past]);
     r("たくなかった", &[Tai, Past, Negative]);
+    r("たがる", &[Tagaru]);
+    r("そう", &[Sou]);
+    r("なさそう", &[Sou, Negative]);
+    r("ず", &[Zu]);
+    r("ずに", &[ZuNi]);
+    r("ぬ", &[Nu]);
+    r("ながら", &[Nagara]);
+    r("たり", &[Tari]);
+    r("なかったり", &[Tari, Negative]);
+    r("すぎる", &[Sugiru]);
+    r("やすい", &[Yasui]);
+    r("にくい", &[Nikui]);
 }
 
 pub fn ichidan_te(mut r: impl FnMut(&'static str, &[Form])) {
@@ -66,14 +88,14 @@ pub(crate) fn godan_lit(g: &'static Godan, mut r: impl FnMut(&'static str, &'sta
     r("", g.u, &[]);
     r("", g.past, &[Past]);
     r("", g.past_conditional, &[Conditional]);
-    r("", g.e, &[Command]);
-    r(g.i, "ます", &[Polite]);
+    r("", g.command, &[Command]);
+    r(g.polite, "ます", &[Polite]);
     r(g.a, "ない", &[Negative]);
-    r(g.i, "ません", &[Negative, Polite]);
-    r(g.i, "ました", &[Past, Polite]);
+    r(g.polite, "ません", &[Negative, Polite]);
+    r(g.polite, "ました", &[Past, Polite]);
     r(g.a, "なかった", &[Past, Negative]);
-    r(g.i, "ませんでした", &[Past, Negative, Polite]);
-    r(g.i, "なさい", &[Command, Polite]);
+    r(g.polite, "ませんでした", &[Past, Negative, Polite]);
+    r(g.i, "なさい", &[Command, Polite, Nasai]);
     r(g.te, "ください", &[Command, Polite, Kudasai]);
     r(g.e, "よ", &[Command, Yo]);
     r(g.u, "な", &[Command, Negative]);
@@ -81,9 +103,11 @@ pub(crate) fn godan_lit(g: &'static Godan, mut r: impl FnMut(&'static str, &'sta
     r(g.e, "ば", &[Hypothetical]);
     r(g.a, "なければ", &[Hypothetical, Negative]);
     r(g.a, "なきゃ", &[Hypothetical, Negative, Kya]);
-    r(g.i, "ましたら", &[Conditional, Polite]);
+    r(g.a, "なくちゃ", &[Hypothetical, Negative, Kucha]);
+    r(g.a, "なけりゃ", &[Hypothetical, Negative, Short]);
+    r(g.polite, "ましたら", &[Conditional, Polite]);
     r(g.a, "なかったら", &[Conditional, Negative]);
-    r(g.i, "ませんでしたら", &[Conditional, Negative, Polite]);
+    r(g.polite, "ませんでしたら", &[Conditional, Negative, Polite]);
     r(g.a, "れる", &[Passive]);
     r(g.a, "れます", &[Passive, Polite]);
     r(g.a, "れない", &[Passive, Negative]);
@@ -99,16 +123,48 @@ pub(crate) fn godan_lit(g: &'static Godan, mut r: impl FnMut(&'static str, &'sta
     r(g.e, "なかった", &[Potential, Past, Negative]);
     r(g.e, "ませんでした", &[Potential, Past, Negative, Polite]);
     r(g.o, "う", &[Volitional]);
-    r(g.i, "ましょう", &[Volitional, Polite]);
+    r(g.polite, "ましょう", &[Volitional, Polite]);
     r(g.u, "だろう", &[Volitional, Darou]);
     r(g.u, "でしょう", &[Volitional, Darou, Polite]);
     r(g.a, "ないだろう", &[Volitional, Negative]);
     r(g.a, "ないでしょう", &[Volitional, Negative, Polite]);
     r(g.a, "せる", &[Causative]);
+    r(g.a, "せられる", &[Causative, Passive]);
+    r(g.a, "せられます", &[Causative, Passive, Polite]);
+    r(g.a, "せられない", &[Causative, Passive, Negative]);
+    r(g.a, "せられた", &[Causative, Passive, Past]);
+    r(g.a, "せられなかった", &[Causative, Passive, Past, Negative]);
+
+    // The short causative passive isn't used for verbs ending in す, since it
+    // would result in ~さされる.
+    if g.u != "す" {
+        r(g.a, "される", &[Causative, Passive, Short]);
+        r(g.a, "されます", &[Causative, Passive, Polite, Short]);
+        r(g.a, "されない", &[Causative, Passive, Negative, Short]);
+        r(g.a, "された", &[Causative, Passive, Past, Short]);
+        r(
+            g.a,
+            "されなかった",
+            &[Causative, Passive, Past, Negative, Short],
+        );
+    }
+
     r(g.i, "たい", &[Tai]);
     r(g.i, "たくない", &[Tai, Negative]);
     r(g.i, "たかった", &[Tai, Past]);
     r(g.i, "たくなかった", &[Tai, Past, Negative]);
+    r(g.i, "たがる", &[Tagaru]);
+    r(g.i, "そう", &[Sou]);
+    r(g.a, "なさそう", &[Sou, Negative]);
+    r(g.a, "ず", &[Zu]);
+    r(g.a, "ずに", &[ZuNi]);
+    r(g.a, "ぬ", &[Nu]);
+    r(g.i, "ながら", &[Nagara]);
+    r(g.past, "り", &[Tari]);
+    r(g.a, "なかったり", &[Tari, Negative]);
+    r(g.i, "すぎる", &[Sugiru]);
+    r(g.i, "やすい", &[Yasui]);
+    r(g.i, "にくい", &[Nikui]);
 }
 
 pub(crate) fn godan_u(r: impl FnMut(&'static str, &'static str, &[Form])) {
@@ -147,6 +203,13 @@ pub(crate) fn godan_ru_base(mut r: impl FnMut(&'static str, &'static str, &[Form
     godan_lit(godan::RU, r);
 }
 
+pub(crate) fn godan_aru_base(mut r: impl FnMut(&'static str, &'static str, &[Form])) {
+    r("", "り", &[Stem]);
+    r("", "って", &[Te]);
+    r("", "いませ", &[Command, Polite]);
+    godan_lit(godan::ARU, r);
+}
+
 pub(crate) fn godan_ku(r: impl FnMut(&'static str, &'static str, &[Form])) {
     godan_lit(godan::KU, r);
 }
@@ -198,13 +261,15 @@ pub(crate) fn kuru(mut r: impl FnMut(&'static str, &'static str, &[Form])) {
     r("こ", "なかった", &[Past, Negative]);
     r("き", "ませんでした", &[Past, Negative, Polite]);
     r("こ", "い", &[Command]);
-    r("き", "なさい", &[Command, Polite]);
+    r("き", "なさい", &[Command, Polite, Nasai]);
     r("き", "てください", &[Command, Polite, Kudasai]);
     r("く", "るな", &[Command, Negative]);
     r("こ", "ないでください", &[Command, Negative, Polite]);
     r("く", "れば", &[Hypothetical]);
     r("こ", "なければ", &[Hypothetical, Negative]);
     r("こ", "なきゃ", &[Hypothetical, Negative, Kya]);
+    r("こ", "なくちゃ", &[Hypothetical, Negative, Kucha]);
+    r("こ", "なけりゃ", &[Hypothetical, Negative, Short]);
     r("き", "たら", &[Conditional]);
     r("き", "ましたら", &[Conditional, Polite]);
     r("こ", "なかったら", &[Conditional, Negative]);
@@ -226,10 +291,31 @@ pub(crate) fn kuru(mut r: impl FnMut(&'static str, &'static str, &[Form])) {
     r("こ", "させます", &[Causative, Polite]);
     r("こ", "させない", &[Causative, Negative]);
     r("こ", "させません", &[Causative, Negative, Polite]);
+    r("こ", "させられる", &[Causative, Passive]);
+    r("こ", "させられます", &[Causative, Passive, Polite]);
+    r("こ", "させられない", &[Causative, Passive, Negative]);
+    r("こ", "させられた", &[Causative, Passive, Past]);
+    r(
+        "こ",
+        "させられなかった",
+        &[Causative, Passive, Past, Negative],
+    );
     r("き", "たい", &[Tai]);
     r("き", "たくない", &[Tai, Negative]);
     r("き", "たかった", &[Tai, Past]);
     r("き", "たくなかった", &[Tai, Past, Negative]);
+    r("き", "たがる", &[Tagaru]);
+    r("き", "そう", &[Sou]);
+    r("こ", "なさそう", &[Sou, Negative]);
+    r("こ", "ず", &[Zu]);
+    r("こ", "ずに", &[ZuNi]);
+    r("こ", "ぬ", &[Nu]);
+    r("き", "ながら", &[Nagara]);
+    r("き", "たり", &[Tari]);
+    r("こ", "なかったり", &[Tari, Negative]);
+    r("き", "すぎる", &[Sugiru]);
+    r("き", "やすい", &[Yasui]);
+    r("き", "にくい", &[Nikui]);
 }
 
 pub(crate) fn kuru_base(mut r: impl FnMut(&'static str, &'static str, &[Form])) {
@@ -250,7 +336,7 @@ pub(crate) fn suru(mut r: impl FnMut(&'static str, &'static str, &[Form])) {
     r("し", "なかった", &[Past, Negative]);
     r("し", "ませんでした", &[Past, Negative, Polite]);
     r("し", "ろ", &[Command]);
-    r("し", "なさい", &[Command, Polite]);
+    r("し", "なさい", &[Command, Polite, Nasai]);
     r("し", "てください", &[Command, Polite, Kudasai]);
     r("し", "よ", &[Command, Yo]);
     r("す", "るな", &[Command, Negative]);
@@ -258,6 +344,8 @@ pub(crate) fn suru(mut r: impl FnMut(&'static str, &'static str, &[Form])) {
     r("す", "れば", &[Hypothetical]);
     r("し", "なければ", &[Hypothetical, Negative]);
     r("し", "なきゃ", &[Hypothetical, Negative, Kya]);
+    r("し", "なくちゃ", &[Hypothetical, Negative, Kucha]);
+    r("し", "なけりゃ", &[Hypothetical, Negative, Short]);
     r("し", "たら", &[Conditional]);
     r("し", "ましたら", &[Conditional, Polite]);
     r("し", "なかったら", &[Conditional, Negative]);
@@ -288,10 +376,27 @@ pub(crate) fn suru(mut r: impl FnMut(&'static str, &'static str, &[Form])) {
     r("し", "なかっただろう", &[Volitional, Past, Negative]);
     r("し", "なかったでしょう", &[Volitional, Past, Negative, Polite]);
     r("さ", "せる", &[Causative]);
+    r("さ", "せられる", &[Causative, Passive]);
+    r("さ", "せられます", &[Causative, Passive, Polite]);
+    r("さ", "せられない", &[Causative, Passive, Negative]);
+    r("さ", "せられた", &[Causative, Passive, Past]);
+    r("さ", "せられなかった", &[Causative, Passive, Past, Negative]);
     r("し", "たい", &[Tai]);
     r("し", "たくない", &[Tai, Negative]);
     r("し", "たかった", &[Tai, Past]);
     r("し", "たくなかった", &[Tai, Past, Negative]);
+    r("し", "たがる", &[Tagaru]);
+    r("し", "そう", &[Sou]);
+    r("し", "なさそう", &[Sou, Negative]);
+    r("せ", "ず", &[Zu]);
+    r("せ", "ずに", &[ZuNi]);
+    r("せ", "ぬ", &[Nu]);
+    r("し", "ながら", &[Nagara]);
+    r("し", "たり", &[Tari]);
+    r("し", "なかったり", &[Tari, Negative]);
+    r("し", "すぎる", &[Sugiru]);
+    r("し", "やすい", &[Yasui]);
+    r("し", "にくい", &[Nikui]);
 }
 
 pub(crate) fn suru_base(mut r: impl FnMut(&'static str, &'static str, &[Form])) {
@@ -306,6 +411,7 @@ macro_rules! suru {
     ($macro:path, te) => {};
 }
 
+/// Conjugations for an i-adjective.
 pub(crate) fn adjective_i(mut r: impl FnMut(&'static str, &[Form])) {
     r("い", &[]);
     r("いです", &[Polite]);
@@ -313,30 +419,206 @@ pub(crate) fn adjective_i(mut r: impl FnMut(&'static str, &[Form])) {
     r("かったです", &[Past, Polite]);
     r("くない", &[Negative]);
     r("くないです", &[Negative, Polite]);
-    r("なかった", &[Past, Negative]);
-    r("なかったです", &[Past, Negative, Polite]);
+    r("くなかった", &[Past, Negative]);
+    r("くなかったです", &[Past, Negative, Polite]);
+    r("くて", &[Te]);
+    r("くなくて", &[Te, Negative]);
+    r("く", &[Adverb]);
+    r("ければ", &[Hypothetical]);
+    r("くなければ", &[Hypothetical, Negative]);
+    r("かったら", &[Conditional]);
+    r("くなかったら", &[Conditional, Negative]);
+    r("かろう", &[Volitional]);
+    r("いだろう", &[Volitional, Darou]);
+    r("いでしょう", &[Volitional, Darou, Polite]);
+    r("くないだろう", &[Volitional, Negative]);
+    r("くないでしょう", &[Volitional, Negative, Polite]);
+    r("さ", &[Sa]);
+    r("すぎる", &[Sugiru]);
+    r("そう", &[Sou]);
+    r("くなさそう", &[Sou, Negative]);
 }
 
-pub(crate) fn adjective_ii(mut r: impl FnMut(&'static str, &'static str, &[Form])) {
-    r("い", "い", &[]);
-    r("い", "いです", &[Polite]);
+/// Conjugations for the irregular いい / よい adjective, where `present` is
+/// the first kana used in the non-past affirmative forms. All other forms are
+/// based on よ.
+#[rustfmt::skip]
+pub(crate) fn adjective_ii(present: &'static str, mut r: impl FnMut(&'static str, &'static str, &[Form])) {
+    r(present, "い", &[]);
+    r(present, "いです", &[Polite]);
     r("よ", "かった", &[Past]);
     r("よ", "かったです", &[Past, Polite]);
     r("よ", "くない", &[Negative]);
     r("よ", "くないです", &[Negative, Polite]);
-    r("よ", "なかった", &[Past, Negative]);
-    r("よ", "なかったです", &[Past, Negative, Polite]);
+    r("よ", "くなかった", &[Past, Negative]);
+    r("よ", "くなかったです", &[Past, Negative, Polite]);
+    r("よ", "くて", &[Te]);
+    r("よ", "くなくて", &[Te, Negative]);
+    r("よ", "く", &[Adverb]);
+    r("よ", "ければ", &[Hypothetical]);
+    r("よ", "くなければ", &[Hypothetical, Negative]);
+    r("よ", "かったら", &[Conditional]);
+    r("よ", "くなかったら", &[Conditional, Negative]);
+    r("よ", "かろう", &[Volitional]);
+    r(present, "いだろう", &[Volitional, Darou]);
+    r(present, "いでしょう", &[Volitional, Darou, Polite]);
+    r("よ", "くないだろう", &[Volitional, Negative]);
+    r("よ", "くないでしょう", &[Volitional, Negative, Polite]);
+    r("よ", "さ", &[Sa]);
+    r("よ", "すぎる", &[Sugiru]);
+    r("よ", "さそう", &[Sou]);
+    r("よ", "くなさそう", &[Sou, Negative]);
 }
 
-pub(crate) fn adjective_na(mut r: impl FnMut(&'static str, &[Form])) {
+/// Conjugations for the copula だ, as suffixes replacing it.
+pub(crate) fn copula(mut r: impl FnMut(&'static str, &[Form])) {
     r("だ", &[]);
     r("です", &[Polite]);
     r("だった", &[Past]);
     r("でした", &[Past, Polite]);
     r("ではない", &[Negative]);
+    r("じゃない", &[Negative, Short]);
     r("ではありません", &[Negative, Polite]);
+    r("じゃありません", &[Negative, Polite, Short]);
     r("ではなかった", &[Past, Negative]);
+    r("じゃなかった", &[Past, Negative, Short]);
     r("ではありませんでした", &[Past, Negative, Polite]);
+    r("じゃありませんでした", &[Past, Negative, Polite, Short]);
+    r("で", &[Te]);
+    r("ではなくて", &[Te, Negative]);
+    r("じゃなくて", &[Te, Negative, Short]);
+    r("な", &[Attributive]);
+    r("なら", &[Hypothetical]);
+    r("でなければ", &[Hypothetical, Negative]);
+    r("じゃなければ", &[Hypothetical, Negative, Short]);
+    r("だったら", &[Conditional]);
+    r("ではなかったら", &[Conditional, Negative]);
+    r("じゃなかったら", &[Conditional, Negative, Short]);
+    r("だろう", &[Volitional, Darou]);
+    r("でしょう", &[Volitional, Darou, Polite]);
+}
+
+/// Conjugations for the polite copula です, as suffixes replacing it.
+///
+/// These are the polite conjugations of だ, where the politeness is already
+/// part of the dictionary form.
+pub(crate) fn desu(mut r: impl FnMut(&'static str, &[Form])) {
+    copula(|suffix, forms| {
+        if forms.contains(&Polite) {
+            let forms = forms.iter().copied().filter(|&f| f != Polite);
+            r(suffix, &forms.collect::<Vec<_>>());
+        }
+    });
+}
+
+/// Conjugations for a na-adjective, using the copula.
+pub(crate) fn adjective_na(mut r: impl FnMut(&'static str, &[Form])) {
+    copula(&mut r);
+    r("に", &[Adverb]);
+    r("さ", &[Sa]);
+    r("すぎる", &[Sugiru]);
+    r("そう", &[Sou]);
+}
+
+/// Conjugations for the polite auxiliary verb ます, as suffixes replacing it.
+pub(crate) fn masu(mut r: impl FnMut(&'static str, &[Form])) {
+    r("ます", &[]);
+    r("ません", &[Negative]);
+    r("ました", &[Past]);
+    r("ませんでした", &[Past, Negative]);
+    r("ましたら", &[Conditional]);
+    r("ませんでしたら", &[Conditional, Negative]);
+    r("ましょう", &[Volitional]);
+    r("ませ", &[Command]);
+}
+
+/// Test if a row of a table doesn't derive a passive, potential or causative
+/// word, which are not formed from keigo or from other potentials.
+pub(crate) fn underived(forms: &[Form]) -> bool {
+    !forms
+        .iter()
+        .any(|f| matches!(f, Passive | Potential | Causative))
+}
+
+/// Forms which are derived from the main verb rather than from an auxiliary
+/// like the いる of 食べている, like 食べさせている and 食べすぎている.
+pub(crate) const INNER: [Form; 4] = [Causative, Passive, Tagaru, Sugiru];
+
+/// Test if a row of a table can be used by an auxiliary like the いる of
+/// 食べている or the しまう of 食べてしまう, see [`INNER`].
+pub(crate) fn auxiliary(forms: &[Form]) -> bool {
+    !forms.iter().any(|f| INNER.contains(f))
+}
+
+/// Conjugations of the auxiliary ある in constructions like 書いてある.
+///
+/// It has no forms based on its a-stem, since its negative is ない. Since it
+/// describes a state it also has no potential, command or forms like たい and
+/// すぎる.
+pub(crate) fn te_aru(mut r: impl FnMut(&'static str, &'static str, &[Form])) {
+    godan_ru(|prefix, suffix, forms| {
+        let stative = !forms.iter().any(|f| {
+            matches!(
+                f,
+                Potential | Command | Tai | Tagaru | Sugiru | Yasui | Nikui
+            )
+        });
+
+        if prefix != "ら" && auxiliary(forms) && stative {
+            r(prefix, suffix, forms);
+        }
+    });
+}
+
+/// Test if a row of a table can be used for an honorific like お書きになる.
+///
+/// Honorifics show respect to someone else, so they have no volitional and
+/// the only commands are polite requests like お書きになってください.
+pub(crate) fn honorific(forms: &[Form]) -> bool {
+    let volitional =
+        forms.contains(&Volitional) && !forms.iter().any(|f| matches!(f, Darou | Negative));
+    let command = forms.contains(&Command) && (!forms.contains(&Polite) || forms.contains(&Nasai));
+    keigo(forms) && !volitional && !command
+}
+
+/// Test if a row of a table can be used for a humble form like お書きする,
+/// which refers to yourself and therefore has no commands.
+pub(crate) fn humble(forms: &[Form]) -> bool {
+    keigo(forms) && !forms.contains(&Command)
+}
+
+/// Test if a row of a table can be used for the passive when it doubles as an
+/// honorific, like 書かれる. Forms like 書かれたい and 書かれてください only
+/// read as passives.
+pub(crate) fn passive_honorific(forms: &[Form]) -> bool {
+    honorific(forms)
+        && !forms
+            .iter()
+            .any(|f| matches!(f, Command | Tai | Yasui | Nikui | Sou))
+}
+
+/// Keigo isn't used to form derived words, or the desire of others and
+/// excess expressed through たがる and すぎる.
+fn keigo(forms: &[Form]) -> bool {
+    underived(forms) && !forms.iter().any(|f| matches!(f, Tagaru | Sugiru))
+}
+
+/// Conjugations for a classical verb, which are all marked as [`Classical`].
+pub(crate) fn classical(c: &Bases, mut r: impl FnMut(Base, &'static str, &[Form])) {
+    r(c.renyou, "", &[Stem, Classical]);
+    r(c.renyou, "て", &[Te, Classical]);
+    r(c.renyou, "き", &[Past, Classical]);
+    r(c.mizen, "ず", &[Zu, Classical]);
+    r(c.mizen, "ざる", &[Zu, Attributive, Classical]);
+    r(c.mizen, "ぬ", &[Nu, Classical]);
+    r(c.mizen, "ば", &[Hypothetical, Classical]);
+    r(c.izen, "ば", &[Conditional, Classical]);
+    r(c.mizen, "む", &[Volitional, Classical]);
+    r(c.mizen, "ん", &[Volitional, Short, Classical]);
+    r(c.mizen, "じ", &[Volitional, Negative, Classical]);
+    r(c.rentai, "", &[Attributive, Classical]);
+    r(c.meirei, "", &[Command, Classical]);
+    r(c.beshi, "べし", &[Beshi, Classical]);
 }
 
 /// Helper to construct a particular [`Inflection`].
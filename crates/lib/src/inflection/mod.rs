@@ -1,10 +1,19 @@
+mod classical;
 mod godan;
 #[macro_use]
 mod macros;
 
 pub use self::conjugate::{conjugate, Kind, Reading};
+pub(crate) use self::conjugate::{find_reading, parts_of_speech};
 mod conjugate;
 
+pub(crate) use self::deinflect::stems;
+pub use self::deinflect::{deinflect, Deinflection};
+mod deinflect;
+
+pub use self::explain::{explain, Explanation, Step};
+mod explain;
+
 use std::fmt;
 use std::ops::{BitAndAssign, BitOr};
 use std::{collections::BTreeMap, ops::BitXor};
@@ -79,10 +88,48 @@ pub enum Form {
     Darou,
     /// Alternate command form using yo.
     Yo,
+    /// Alternate command form using nasai.
+    Nasai,
+    /// Appearance, looks like ~.
+    Sou,
+    /// Classical negative using zu.
+    Zu,
+    /// Classical negative using zu ni, without doing ~.
+    ZuNi,
+    /// Classical negative using nu.
+    Nu,
+    /// Simultaneous action using nagara.
+    Nagara,
+    /// Non-exhaustive listing of actions using tari.
+    Tari,
+    /// Excess using sugiru.
+    Sugiru,
+    /// Easy to do using yasui.
+    Yasui,
+    /// Hard to do using nikui.
+    Nikui,
+    /// Alternate negative hypothetical form using kucha.
+    Kucha,
+    /// Nominalization using sa.
+    Sa,
+    /// Adverbial form.
+    Adverb,
+    /// Attributive form, used before a noun.
+    Attributive,
+    /// Obligation or conjecture using beshi.
+    Beshi,
+    /// Classical or literary form.
+    Classical,
+    /// Shows signs of wanting using tagaru.
+    Tagaru,
+    /// Respectful language (sonkeigo).
+    Honorific,
+    /// Humble language (kenjougo).
+    Humble,
 }
 
 impl Form {
-    pub const ALL: [Form; 26] = [
+    pub const ALL: [Form; 45] = [
         Form::Stem,
         Form::Short,
         Form::Causative,
@@ -109,6 +156,25 @@ impl Form {
         Form::Kudasai,
         Form::Darou,
         Form::Yo,
+        Form::Nasai,
+        Form::Sou,
+        Form::Zu,
+        Form::ZuNi,
+        Form::Nu,
+        Form::Nagara,
+        Form::Tari,
+        Form::Sugiru,
+        Form::Yasui,
+        Form::Nikui,
+        Form::Kucha,
+        Form::Sa,
+        Form::Adverb,
+        Form::Attributive,
+        Form::Beshi,
+        Form::Classical,
+        Form::Tagaru,
+        Form::Honorific,
+        Form::Humble,
     ];
 
     /// Longer title for the form.
@@ -142,6 +208,25 @@ impl Form {
             Form::Kudasai => "alternate form using ~kudasai",
             Form::Darou => "alternate form using ~darou / ~deshou",
             Form::Yo => "alternate command form using ~yo",
+            Form::Nasai => "alternate command form using ~nasai, do ~",
+            Form::Sou => "~sou, looks like ~, seems like ~",
+            Form::Zu => "~zu, classical negative, not ~",
+            Form::ZuNi => "~zu ni, without doing ~",
+            Form::Nu => "~nu, classical negative, not ~",
+            Form::Nagara => "~nagara, while doing ~",
+            Form::Tari => "~tari, doing things such as ~",
+            Form::Sugiru => "~sugiru, to do ~ too much",
+            Form::Yasui => "~yasui, easy to ~",
+            Form::Nikui => "~nikui, hard to ~",
+            Form::Kucha => "~kucha, alternative hypothetical negative, must ~",
+            Form::Sa => "~sa, nominalization, the degree of ~",
+            Form::Adverb => "adverbial form, ~ku / ~ni",
+            Form::Attributive => "attributive form, ~na, used before a noun",
+            Form::Beshi => "~beshi, should ~, must ~",
+            Form::Classical => "classical or literary form",
+            Form::Tagaru => "~tagaru, shows signs of wanting to ~",
+            Form::Honorific => "honorific, o~ ni naru / ~rareru, respectful about someone else",
+            Form::Humble => "humble, o~ suru, humble about yourself",
         }
     }
 
@@ -174,6 +259,25 @@ impl Form {
             Form::Kudasai => "kudasai",
             Form::Darou => "~darou / ~deshou",
             Form::Yo => "~yo",
+            Form::Nasai => "~nasai",
+            Form::Sou => "~sou",
+            Form::Zu => "~zu",
+            Form::ZuNi => "~zu ni",
+            Form::Nu => "~nu",
+            Form::Nagara => "~nagara",
+            Form::Tari => "~tari",
+            Form::Sugiru => "~sugiru",
+            Form::Yasui => "~yasui",
+            Form::Nikui => "~nikui",
+            Form::Kucha => "~kucha",
+            Form::Sa => "~sa",
+            Form::Adverb => "adverb",
+            Form::Attributive => "attributive",
+            Form::Beshi => "~beshi",
+            Form::Classical => "classical",
+            Form::Tagaru => "~tagaru",
+            Form::Honorific => "honorific",
+            Form::Humble => "humble",
         }
     }
 }
@@ -267,6 +371,12 @@ impl Inflection {
         self.form.contains(f)
     }
 
+    /// Test if the inflection is an honorific or humble (keigo) form.
+    #[inline]
+    pub fn is_keigo(&self) -> bool {
+        self.form.contains(Form::Honorific) || self.form.contains(Form::Humble)
+    }
+
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = Form> {
         self.form.iter()
@@ -314,6 +424,10 @@ pub struct Inflections<'a> {
     pub dictionary: Full<'a>,
     #[borrowme(owned = BTreeMap<Inflection, OwnedFull>, with = self::inflections)]
     pub inflections: BTreeMap<Inflection, Fragments<'a>>,
+    /// Colloquial contractions, like 食べてる for 食べている, as alternate
+    /// surface forms of the inflection they contract.
+    #[borrowme(owned = BTreeMap<Inflection, OwnedFull>, with = self::inflections)]
+    pub colloquial: BTreeMap<Inflection, Fragments<'a>>,
 }
 
 impl<'a> Inflections<'a> {
@@ -321,22 +435,23 @@ impl<'a> Inflections<'a> {
         Self {
             dictionary,
             inflections: BTreeMap::new(),
+            colloquial: BTreeMap::new(),
         }
     }
 
     /// Insert a value into this collection of inflections.
     pub(crate) fn insert(&mut self, inflect: &[Form], inflect2: &[Form], word: Fragments<'a>) {
-        let mut form = crate::macro_support::fixed_map::Set::new();
-
-        for f in inflect {
-            form.insert(*f);
-        }
-
-        for f in inflect2 {
-            form.insert(*f);
-        }
+        self.inflections.insert(forms(inflect, inflect2), word);
+    }
 
-        self.inflections.insert(crate::Inflection::new(form), word);
+    /// Insert a colloquial contraction of the given inflection.
+    pub(crate) fn insert_colloquial(
+        &mut self,
+        inflect: &[Form],
+        inflect2: &[Form],
+        word: Fragments<'a>,
+    ) {
+        self.colloquial.insert(forms(inflect, inflect2), word);
     }
 
     /// Test if any polite inflections exist.
@@ -364,6 +479,21 @@ impl<'a> Inflections<'a> {
     pub fn iter(&self) -> impl Iterator<Item = (&Inflection, &Fragments<'a>)> + '_ {
         self.inflections.iter()
     }
+
+    /// Iterate over all colloquial contractions.
+    pub fn iter_colloquial(&self) -> impl Iterator<Item = (&Inflection, &Fragments<'a>)> + '_ {
+        self.colloquial.iter()
+    }
+}
+
+fn forms(inflect: &[Form], inflect2: &[Form]) -> Inflection {
+    let mut form = Set::new();
+
+    for f in inflect.iter().chain(inflect2) {
+        form.insert(*f);
+    }
+
+    Inflection::new(form)
 }
 
 impl OwnedInflections {
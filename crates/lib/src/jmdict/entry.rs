@@ -21,6 +21,8 @@ struct Weight {
     conjugation: f32,
     #[allow(unused)]
     length: f32,
+    #[allow(unused)]
+    distance: f32,
 }
 
 impl PartialEq for Weight {
@@ -55,6 +57,14 @@ pub struct EntryKey {
     sequence: u64,
 }
 
+impl EntryKey {
+    /// The combined weight of the entry, where a higher weight indicates a
+    /// better match.
+    pub fn weight(&self) -> f32 {
+        self.weight.weight
+    }
+}
+
 #[borrowme::borrowme]
 #[derive(Clone, Debug, Serialize, Deserialize, Encode, Decode)]
 #[musli(packed)]
@@ -73,7 +83,10 @@ pub struct Entry<'a> {
 
 impl Entry<'_> {
     /// Entry weight.
-    pub fn sort_key(&self, input: &str, conjugation: bool) -> EntryKey {
+    ///
+    /// The `distance` is the edit distance the entry was matched with, which
+    /// is penalized.
+    pub fn sort_key(&self, input: &str, conjugation: bool, distance: u8) -> EntryKey {
         // Boost based on exact query.
         let mut query = 1.0f32;
         // Store the priority which performs the maximum boost.
@@ -84,6 +97,8 @@ impl Entry<'_> {
         let conjugation = conjugation.then_some(1.2).unwrap_or(1.0);
         // Calculate length boost.
         let length = (input.chars().count().min(10) as f32 / 10.0) * 1.2;
+        // Penalty for matches which are not exact.
+        let distance = 1.0 / (1.0 + distance as f32);
 
         for element in &self.reading_elements {
             if element.text == input {
@@ -119,12 +134,13 @@ impl Entry<'_> {
 
         EntryKey {
             weight: Weight {
-                weight: query * priority * sense_count * conjugation * length,
+                weight: query * priority * sense_count * conjugation * length * distance,
                 query,
                 priority,
                 sense_count,
                 conjugation,
                 length,
+                distance,
             },
             sequence: self.sequence,
         }
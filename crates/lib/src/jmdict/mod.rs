@@ -46,7 +46,7 @@ macro_rules! builder {
     }
 }
 
-pub use self::parser::Parser;
+pub use self::parser::{Chunk, Parser, Reader};
 mod parser;
 
 pub(crate) mod empty;
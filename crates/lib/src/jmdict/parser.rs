@@ -1,9 +1,11 @@
+use std::io::Read;
 use std::mem;
 
 use anyhow::{bail, Context, Result};
 use relative_path::RelativePathBuf;
 use xmlparser::{ElementEnd, Token, Tokenizer};
 
+use crate::chunks::Chunks;
 use crate::jmdict::entry;
 use crate::jmdict::Entry;
 
@@ -46,6 +48,8 @@ pub struct Parser<'a> {
     path: RelativePathBuf,
     input: &'a str,
     tokenizer: Tokenizer<'a>,
+    partial: bool,
+    created: Option<&'a str>,
 }
 
 impl<'a> Parser<'a> {
@@ -56,9 +60,32 @@ impl<'a> Parser<'a> {
             path: RelativePathBuf::new(),
             input,
             tokenizer: Tokenizer::from(input),
+            partial: false,
+            created: None,
         }
     }
 
+    /// Construct a parser over a chunk of a larger document, which might end
+    /// before the root element is closed. If `started` is set, the chunk
+    /// starts inside of the root element.
+    fn chunk(input: &'a str, started: bool) -> Self {
+        let mut parser = Self::new(input);
+        parser.partial = true;
+
+        if started {
+            parser.state = State::Root;
+            parser.tokenizer = Tokenizer::from_fragment(input, 0..input.len());
+        }
+
+        parser
+    }
+
+    /// The creation date of the dictionary, as specified in the `JMdict
+    /// created` comment. Available once the first entry has been parsed.
+    pub fn created(&self) -> Option<&'a str> {
+        self.created
+    }
+
     /// Parse the next entry.
     pub fn parse(&mut self) -> Result<Option<Entry<'a>>> {
         loop {
@@ -87,6 +114,9 @@ impl<'a> Parser<'a> {
                             self.state = State::Initial;
                             return Ok(None);
                         }
+                        Output::Eof if self.partial => {
+                            return Ok(None);
+                        }
                         output => {
                             bail!("expected `entry` element, but found {output:?}");
                         }
@@ -143,6 +173,13 @@ impl<'a> Parser<'a> {
                         return Ok(Output::Close);
                     }
                 }
+                Token::Comment { text, .. } => {
+                    let text = &self.input[text.range()];
+
+                    if let Some(created) = text.trim().strip_prefix("JMdict created:") {
+                        self.created = Some(created.trim());
+                    }
+                }
                 Token::Attribute { local, value, .. } => {
                     let local = &self.input[local.range()];
                     let value = &self.input[value.range()];
@@ -155,3 +192,48 @@ impl<'a> Parser<'a> {
         }
     }
 }
+
+/// Reads JMdict incrementally, providing a [`Chunk`] for each part of the
+/// input.
+pub struct Reader<R> {
+    chunks: Chunks<R>,
+    started: bool,
+}
+
+impl<R> Reader<R>
+where
+    R: Read,
+{
+    pub fn new(reader: R) -> Self {
+        Self {
+            chunks: Chunks::new(reader, &["</entry>"]),
+            started: false,
+        }
+    }
+
+    /// Get the next chunk of input, or `None` if the input has been consumed.
+    pub fn next_chunk(&mut self) -> Result<Option<Chunk>> {
+        let Some(input) = self.chunks.next()? else {
+            return Ok(None);
+        };
+
+        Ok(Some(Chunk {
+            input: input.to_owned(),
+            started: mem::replace(&mut self.started, true),
+        }))
+    }
+}
+
+/// An owned chunk of input read by a [`Reader`], which can be parsed
+/// independently of other chunks.
+pub struct Chunk {
+    input: String,
+    started: bool,
+}
+
+impl Chunk {
+    /// Get a parser for the chunk.
+    pub fn parser(&self) -> Parser<'_> {
+        Parser::chunk(&self.input, self.started)
+    }
+}
@@ -10,6 +10,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::entities::ReadingInfo;
+use crate::furigana::Alignment;
 use crate::jmdict::empty;
 use crate::jmdict::text;
 
@@ -29,6 +30,14 @@ pub struct ReadingElement<'a> {
     #[musli(with = crate::musli::set::<_>)]
     #[copy]
     pub info: Set<ReadingInfo>,
+    /// Pitch accents of the reading, as the mora after which the pitch drops.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accent: Vec<u8>,
+    /// Per-kanji alignments of the reading to the kanji elements it applies
+    /// to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[borrowed_attr(serde(borrow))]
+    pub furigana: Vec<Alignment<'a>>,
 }
 
 impl<'a> ReadingElement<'a> {
@@ -55,6 +64,16 @@ impl<'a> ReadingElement<'a> {
 
         self.reading_string.contains(text)
     }
+
+    /// Get the per-kanji reading lengths of the given kanji element, or an
+    /// empty slice if the reading hasn't been aligned to it.
+    pub fn alignment(&self, kanji: &str) -> &[u8] {
+        self.furigana
+            .iter()
+            .find(|a| a.kanji == kanji)
+            .map(|a| a.lengths.as_slice())
+            .unwrap_or_default()
+    }
 }
 
 impl OwnedReadingElement {
@@ -70,6 +89,16 @@ impl OwnedReadingElement {
 
         self.reading_string.contains(text)
     }
+
+    /// Get the per-kanji reading lengths of the given kanji element, or an
+    /// empty slice if the reading hasn't been aligned to it.
+    pub fn alignment(&self, kanji: &str) -> &[u8] {
+        self.furigana
+            .iter()
+            .find(|a| a.kanji == kanji)
+            .map(|a| a.lengths.as_slice())
+            .unwrap_or_default()
+    }
 }
 
 struct DebugSparse<'a>(&'a ReadingElement<'a>);
@@ -96,6 +125,14 @@ impl fmt::Debug for DebugSparse<'_> {
             f.field("info", &self.0.info);
         }
 
+        if !self.0.accent.is_empty() {
+            f.field("accent", &self.0.accent);
+        }
+
+        if !self.0.furigana.is_empty() {
+            f.field("furigana", &self.0.furigana);
+        }
+
         f.finish_non_exhaustive()
     }
 }
@@ -157,6 +194,8 @@ impl<'a> Builder<'a> {
             reading_string,
             priority,
             info,
+            accent: Vec::new(),
+            furigana: Vec::new(),
         })
     }
 }
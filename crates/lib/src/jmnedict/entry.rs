@@ -0,0 +1,77 @@
+use std::mem;
+
+use anyhow::{Context, Result};
+use musli::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+use crate::jmnedict::{kanji_element, reading_element, text, translation};
+use crate::jmnedict::{KanjiElement, ReadingElement, Translation};
+
+#[borrowme::borrowme]
+#[derive(Clone, Debug, Serialize, Deserialize, Encode, Decode)]
+#[musli(packed)]
+pub struct Entry<'a> {
+    pub sequence: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[borrowed_attr(serde(borrow))]
+    pub kanji_elements: Vec<KanjiElement<'a>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[borrowed_attr(serde(borrow))]
+    pub reading_elements: Vec<ReadingElement<'a>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[borrowed_attr(serde(borrow))]
+    pub translations: Vec<Translation<'a>>,
+}
+
+#[derive(Debug, Default)]
+enum State<'a> {
+    #[default]
+    Root,
+    EntrySequence(text::Builder<'a>),
+    KanjiElement(kanji_element::Builder<'a>),
+    ReadingElement(reading_element::Builder<'a>),
+    Translation(translation::Builder<'a>),
+}
+
+#[derive(Default)]
+pub(crate) struct Builder<'a> {
+    state: State<'a>,
+    sequence: Option<u64>,
+    kanji_elements: Vec<KanjiElement<'a>>,
+    reading_elements: Vec<ReadingElement<'a>>,
+    translations: Vec<Translation<'a>>,
+}
+
+impl<'a> Builder<'a> {
+    builder! {
+        self => Entry<'a> {
+            "ent_seq", EntrySequence, value => {
+                self.sequence = Some(value.parse().context("Invalid sequence")?);
+            }
+            "k_ele", KanjiElement, value => {
+                self.kanji_elements.push(value);
+            }
+            "r_ele", ReadingElement, value => {
+                self.reading_elements.push(value);
+            }
+            "trans", Translation, value => {
+                self.translations.push(value);
+            }
+        }
+    }
+
+    /// Build an [`Entry`].
+    fn build(&mut self) -> Result<Entry<'a>> {
+        let sequence = self.sequence.take().context("missing entry sequence")?;
+        let kanji_elements = mem::take(&mut self.kanji_elements);
+        let reading_elements = mem::take(&mut self.reading_elements);
+        let translations = mem::take(&mut self.translations);
+
+        Ok(Entry {
+            sequence,
+            kanji_elements,
+            reading_elements,
+            translations,
+        })
+    }
+}
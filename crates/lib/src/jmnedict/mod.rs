@@ -0,0 +1,68 @@
+//! Parser for JMnedict, the dictionary of Japanese proper names.
+
+macro_rules! ready {
+    ($expr:expr) => {
+        match $expr? {
+            crate::jmnedict::parser::Poll::Ready(ready) => ready,
+            crate::jmnedict::parser::Poll::Pending => {
+                return Ok(crate::jmnedict::parser::Poll::Pending)
+            }
+        }
+    };
+}
+
+macro_rules! builder {
+    ($self:ident => $return:ty { $($name:expr, $variant:ident, $var:pat => $action:block)* }) => {
+        pub(crate) fn wants_text(&self) -> bool {
+            match &self.state {
+                State::Root => false,
+                $(State::$variant(builder) => builder.wants_text(),)*
+            }
+        }
+
+        pub(crate) fn poll(&mut $self, output: crate::jmnedict::parser::Output<'a>) -> Result<crate::jmnedict::parser::Poll<$return>> {
+            tracing::trace!(state = ?$self.state, ?output);
+
+            match &mut $self.state {
+                State::Root => match output {
+                    $(crate::jmnedict::parser::Output::Open($name) => {
+                        $self.state = State::$variant(Default::default());
+                        return Ok(crate::jmnedict::parser::Poll::Pending);
+                    })*
+                    crate::jmnedict::parser::Output::Close => {
+                        return Ok(crate::jmnedict::parser::Poll::Ready($self.build()?));
+                    }
+                    output => {
+                        ::anyhow::bail!("Unsupported {output:?}")
+                    }
+                }
+                $(State::$variant(builder) => {
+                    let span = ::tracing::info_span!($name);
+                    let _enter = span.enter();
+                    #[allow(clippy::let_unit_value)]
+                    let $var = ready!(builder.poll(output));
+                    $action;
+                    $self.state = State::Root;
+                    return Ok(crate::jmnedict::parser::Poll::Pending);
+                })*
+            }
+        }
+    }
+}
+
+pub use self::parser::{Chunk, Parser, Reader};
+mod parser;
+
+pub use self::entry::{Entry, OwnedEntry};
+pub(crate) mod entry;
+
+pub use self::kanji_element::{KanjiElement, OwnedKanjiElement};
+pub(crate) mod kanji_element;
+
+pub use self::reading_element::{OwnedReadingElement, ReadingElement};
+pub(crate) mod reading_element;
+
+pub use self::translation::{OwnedTranslation, Translation};
+pub(crate) mod translation;
+
+pub(crate) mod text;
@@ -0,0 +1,219 @@
+use std::io::Read;
+use std::mem;
+
+use anyhow::{bail, Context, Result};
+use relative_path::RelativePathBuf;
+use xmlparser::{ElementEnd, Token, Tokenizer};
+
+use crate::chunks::Chunks;
+use crate::jmnedict::entry;
+use crate::jmnedict::Entry;
+
+pub(crate) enum Poll<T> {
+    Ready(T),
+    Pending,
+}
+
+enum State<'a> {
+    /// Initial parser state.
+    Initial,
+    /// Inside of the root node.
+    Root,
+    /// Building an entry.
+    Entry(entry::Builder<'a>),
+}
+
+impl State<'_> {
+    fn wants_text(&self) -> bool {
+        match self {
+            State::Initial => false,
+            State::Root => false,
+            State::Entry(entry) => entry.wants_text(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Output<'a> {
+    Text(&'a str),
+    Open(&'a str),
+    Close,
+    Eof,
+}
+
+pub struct Parser<'a> {
+    state: State<'a>,
+    closed: bool,
+    path: RelativePathBuf,
+    input: &'a str,
+    tokenizer: Tokenizer<'a>,
+    partial: bool,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            state: State::Initial,
+            closed: false,
+            path: RelativePathBuf::new(),
+            input,
+            tokenizer: Tokenizer::from(input),
+            partial: false,
+        }
+    }
+
+    /// Construct a parser over a chunk of a larger document, which might end
+    /// before the root element is closed. If `started` is set, the chunk
+    /// starts inside of the root element.
+    fn chunk(input: &'a str, started: bool) -> Self {
+        let mut parser = Self::new(input);
+        parser.partial = true;
+
+        if started {
+            parser.state = State::Root;
+            parser.tokenizer = Tokenizer::from_fragment(input, 0..input.len());
+        }
+
+        parser
+    }
+
+    /// Parse the next entry.
+    pub fn parse(&mut self) -> Result<Option<Entry<'a>>> {
+        loop {
+            let output = self.parse_next()?;
+
+            macro_rules! open {
+                ($element:pat) => {
+                    if !matches!(output, Output::Open($element)) {
+                        bail!(
+                            "expected {} element, but found {output:?}",
+                            stringify!($element)
+                        );
+                    }
+                };
+            }
+
+            match &mut self.state {
+                State::Initial => {
+                    open!("JMnedict");
+                    self.state = State::Root;
+                }
+                State::Root => {
+                    match output {
+                        Output::Open("entry") => {}
+                        Output::Close => {
+                            self.state = State::Initial;
+                            return Ok(None);
+                        }
+                        Output::Eof if self.partial => {
+                            return Ok(None);
+                        }
+                        output => {
+                            bail!("expected `entry` element, but found {output:?}");
+                        }
+                    }
+
+                    self.state = State::Entry(entry::Builder::default());
+                }
+                State::Entry(builder) => {
+                    let span = tracing::info_span!("entry", path = ?self.path);
+                    let _enter = span.enter();
+
+                    if let Poll::Ready(entry) =
+                        builder.poll(output).with_context(|| self.path.to_owned())?
+                    {
+                        self.state = State::Root;
+                        return Ok(Some(entry));
+                    }
+                }
+            }
+        }
+    }
+
+    fn parse_next(&mut self) -> Result<Output<'a>> {
+        loop {
+            if mem::take(&mut self.closed) {
+                self.path.pop();
+            }
+
+            let Some(token) = self.tokenizer.next() else {
+                return Ok(Output::Eof);
+            };
+
+            let wants_text = self.state.wants_text();
+
+            match token? {
+                Token::Text { text } if wants_text => {
+                    let text = &self.input[text.range()];
+                    return Ok(Output::Text(text));
+                }
+                Token::Cdata { text, .. } => {
+                    let text = &self.input[text.range()];
+                    return Ok(Output::Text(text));
+                }
+                Token::ElementStart { local, .. } => {
+                    let local = &self.input[local.range()];
+                    self.path.push(local);
+                    tracing::trace!(path = self.path.as_str(), "enter");
+                    return Ok(Output::Open(local));
+                }
+                Token::ElementEnd {
+                    end: ElementEnd::Close { .. } | ElementEnd::Empty { .. },
+                    ..
+                } => {
+                    tracing::trace!(path = self.path.as_str(), "leave");
+                    self.closed = true;
+                    return Ok(Output::Close);
+                }
+                _ => {
+                    // intentionally ignore unsupported data.
+                }
+            }
+        }
+    }
+}
+
+/// Reads JMnedict incrementally, providing a [`Chunk`] for each part of the
+/// input.
+pub struct Reader<R> {
+    chunks: Chunks<R>,
+    started: bool,
+}
+
+impl<R> Reader<R>
+where
+    R: Read,
+{
+    pub fn new(reader: R) -> Self {
+        Self {
+            chunks: Chunks::new(reader, &["</entry>"]),
+            started: false,
+        }
+    }
+
+    /// Get the next chunk of input, or `None` if the input has been consumed.
+    pub fn next_chunk(&mut self) -> Result<Option<Chunk>> {
+        let Some(input) = self.chunks.next()? else {
+            return Ok(None);
+        };
+
+        Ok(Some(Chunk {
+            input: input.to_owned(),
+            started: mem::replace(&mut self.started, true),
+        }))
+    }
+}
+
+/// An owned chunk of input read by a [`Reader`], which can be parsed
+/// independently of other chunks.
+pub struct Chunk {
+    input: String,
+    started: bool,
+}
+
+impl Chunk {
+    /// Get a parser for the chunk.
+    pub fn parser(&self) -> Parser<'_> {
+        Parser::chunk(&self.input, self.started)
+    }
+}
@@ -0,0 +1,82 @@
+use core::mem;
+
+use anyhow::ensure;
+use anyhow::{anyhow, Context, Result};
+use fixed_map::Set;
+use musli::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::ReadingInfo;
+use crate::jmnedict::text;
+use crate::priority::Priority;
+
+#[borrowme::borrowme]
+#[derive(Clone, Debug, Serialize, Deserialize, Encode, Decode)]
+#[musli(packed)]
+pub struct ReadingElement<'a> {
+    pub text: &'a str,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[borrowed_attr(serde(borrow))]
+    pub reading_string: Vec<&'a str>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub priority: Vec<Priority>,
+    #[serde(default, skip_serializing_if = "Set::is_empty")]
+    #[musli(with = crate::musli::set::<_>)]
+    #[copy]
+    pub info: Set<ReadingInfo>,
+}
+
+#[derive(Debug, Default)]
+enum State<'a> {
+    #[default]
+    Root,
+    Text(text::Builder<'a>),
+    ReadingString(text::Builder<'a>),
+    Priority(text::Builder<'a>),
+    Information(text::Builder<'a>),
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Builder<'a> {
+    state: State<'a>,
+    text: Option<&'a str>,
+    reading_string: Vec<&'a str>,
+    priority: Vec<Priority>,
+    info: Set<ReadingInfo>,
+}
+
+impl<'a> Builder<'a> {
+    builder! {
+        self => ReadingElement<'a> {
+            "reb", Text, value => {
+                ensure!(self.text.is_none(), "Only one reb element allowed");
+                self.text = Some(value);
+            }
+            "re_restr", ReadingString, value => {
+                self.reading_string.push(value);
+            }
+            "re_pri", Priority, value => {
+                let priority = Priority::parse(value).with_context(|| anyhow!("Unsupported priority `{value}`"))?;
+                self.priority.push(priority);
+            }
+            "re_inf", Information, value => {
+                let info = ReadingInfo::parse(value).with_context(|| anyhow!("Unsupported info `{value}`"))?;
+                self.info.insert(info);
+            }
+        }
+    }
+
+    fn build(&mut self) -> Result<ReadingElement<'a>> {
+        let text = self.text.context("missing text")?;
+        let reading_string = mem::take(&mut self.reading_string);
+        let priority = mem::take(&mut self.priority);
+        let info = mem::take(&mut self.info);
+
+        Ok(ReadingElement {
+            text,
+            reading_string,
+            priority,
+            info,
+        })
+    }
+}
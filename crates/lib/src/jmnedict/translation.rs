@@ -0,0 +1,67 @@
+use core::mem;
+
+use anyhow::{anyhow, Context, Result};
+use fixed_map::Set;
+use musli::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::NameType;
+use crate::jmnedict::text;
+
+#[borrowme::borrowme]
+#[derive(Clone, Debug, Serialize, Deserialize, Encode, Decode)]
+#[musli(packed)]
+pub struct Translation<'a> {
+    #[serde(default, skip_serializing_if = "Set::is_empty")]
+    #[musli(with = crate::musli::set::<_>)]
+    #[copy]
+    pub name_type: Set<NameType>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[borrowed_attr(serde(borrow))]
+    pub xref: Vec<&'a str>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[borrowed_attr(serde(borrow))]
+    pub text: Vec<&'a str>,
+}
+
+#[derive(Debug, Default)]
+enum State<'a> {
+    #[default]
+    Root,
+    NameType(text::Builder<'a>),
+    Xref(text::Builder<'a>),
+    Text(text::Builder<'a>),
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Builder<'a> {
+    state: State<'a>,
+    name_type: Set<NameType>,
+    xref: Vec<&'a str>,
+    text: Vec<&'a str>,
+}
+
+impl<'a> Builder<'a> {
+    builder! {
+        self => Translation<'a> {
+            "name_type", NameType, value => {
+                let name_type = NameType::parse(value).with_context(|| anyhow!("Unsupported name type `{value}`"))?;
+                self.name_type.insert(name_type);
+            }
+            "xref", Xref, value => {
+                self.xref.push(value);
+            }
+            "trans_det", Text, value => {
+                self.text.push(value);
+            }
+        }
+    }
+
+    fn build(&mut self) -> Result<Translation<'a>> {
+        Ok(Translation {
+            name_type: mem::take(&mut self.name_type),
+            xref: mem::take(&mut self.xref),
+            text: mem::take(&mut self.text),
+        })
+    }
+}
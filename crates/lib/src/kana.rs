@@ -107,6 +107,34 @@ impl<'a> Fragments<'a> {
         Furigana::inner(self.text.clone(), self.reading.clone(), self.suffix.clone())
     }
 
+    /// Add the given kana in front of both the text and the reading, like the
+    /// お of お書きになる.
+    pub(crate) fn prefix(&self, kana: &'a str) -> Self {
+        Self {
+            text: Concat::new(
+                [kana]
+                    .into_iter()
+                    .chain(self.text.as_slice().iter().copied()),
+            ),
+            reading: Concat::new(
+                [kana]
+                    .into_iter()
+                    .chain(self.reading.as_slice().iter().copied()),
+            ),
+            suffix: self.suffix.clone(),
+        }
+    }
+
+    /// Strip the given kana from the end of the suffix, which is used to get
+    /// the stem of a derived word such as the causative 食べさせる.
+    pub(crate) fn strip_suffix(&self, kana: &str) -> Option<Self> {
+        Some(Self {
+            text: self.text.clone(),
+            reading: self.reading.clone(),
+            suffix: self.suffix.strip_suffix(kana)?,
+        })
+    }
+
     /// Append suffixes to this pair.
     pub(crate) fn concat<I, T>(&self, strings: I) -> Self
     where
@@ -25,9 +25,9 @@ pub(crate) struct Builder<'a> {
 #[derive(Clone, Debug, Serialize, Deserialize, Encode, Decode)]
 #[musli(packed)]
 pub struct Header<'a> {
-    file_version: &'a str,
-    database_version: &'a str,
-    date_of_creation: &'a str,
+    pub file_version: &'a str,
+    pub database_version: &'a str,
+    pub date_of_creation: &'a str,
 }
 
 impl<'a> Builder<'a> {
@@ -43,6 +43,13 @@ pub struct Misc<'a> {
     radical_names: Vec<&'a str>,
 }
 
+impl Misc<'_> {
+    /// The accepted stroke count of the character.
+    pub fn stroke_count(&self) -> Option<u8> {
+        self.stroke_count
+    }
+}
+
 impl<'a> Builder<'a> {
     builder! {
         self => Misc<'a> {
@@ -50,7 +57,10 @@ impl<'a> Builder<'a> {
                 self.grade = Some(value.parse()?);
             }
             "stroke_count", StrokeCount, value => {
-                self.stroke_count = Some(value.parse()?);
+                // Any subsequent stroke counts are common miscounts.
+                if self.stroke_count.is_none() {
+                    self.stroke_count = Some(value.parse()?);
+                }
             }
             "variant", Variant, value => {
                 self.variant = Some(value);
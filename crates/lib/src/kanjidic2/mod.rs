@@ -54,13 +54,13 @@ macro_rules! builder {
 
 mod array;
 
-pub use self::parser::Parser;
+pub use self::parser::{Chunk, Parser, Reader};
 mod parser;
 
 pub use self::character::{Character, OwnedCharacter};
 mod character;
 
-pub use self::header::Header;
+pub use self::header::{Header, OwnedHeader};
 mod header;
 
 pub use self::code_point::CodePoint;
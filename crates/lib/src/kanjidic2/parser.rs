@@ -1,11 +1,13 @@
+use std::io::Read;
 use std::mem;
 
 use anyhow::{bail, Context, Result};
 use relative_path::RelativePathBuf;
 use xmlparser::{ElementEnd, Token, Tokenizer};
 
+use crate::chunks::Chunks;
 use crate::kanjidic2::character::{self, Character};
-use crate::kanjidic2::header;
+use crate::kanjidic2::header::{self, Header};
 
 pub(crate) enum Poll<T> {
     Ready(T),
@@ -49,6 +51,8 @@ pub struct Parser<'a> {
     path: RelativePathBuf,
     input: &'a str,
     tokenizer: Tokenizer<'a>,
+    partial: bool,
+    header: Option<Header<'a>>,
 }
 
 impl<'a> Parser<'a> {
@@ -59,9 +63,32 @@ impl<'a> Parser<'a> {
             path: RelativePathBuf::new(),
             input,
             tokenizer: Tokenizer::from(input),
+            partial: false,
+            header: None,
         }
     }
 
+    /// Construct a parser over a chunk of a larger document, which might end
+    /// before the root element is closed. If `started` is set, the chunk
+    /// starts inside of the root element.
+    fn chunk(input: &'a str, started: bool) -> Self {
+        let mut parser = Self::new(input);
+        parser.partial = true;
+
+        if started {
+            parser.state = State::Root;
+            parser.tokenizer = Tokenizer::from_fragment(input, 0..input.len());
+        }
+
+        parser
+    }
+
+    /// The header of the dictionary. Available once the first character has
+    /// been parsed.
+    pub fn header(&self) -> Option<&Header<'a>> {
+        self.header.as_ref()
+    }
+
     /// Parse the next entry.
     pub fn parse(&mut self) -> Result<Option<Character<'a>>> {
         loop {
@@ -94,6 +121,9 @@ impl<'a> Parser<'a> {
                         self.state = State::Initial;
                         return Ok(None);
                     }
+                    Output::Eof if self.partial => {
+                        return Ok(None);
+                    }
                     output => {
                         bail!("expected `header` or `character` element, but found {output:?}");
                     }
@@ -102,9 +132,10 @@ impl<'a> Parser<'a> {
                     let span = tracing::info_span!("entry", path = ?self.path);
                     let _enter = span.enter();
 
-                    if let Poll::Ready(..) =
+                    if let Poll::Ready(header) =
                         builder.poll(output).with_context(|| self.path.to_owned())?
                     {
+                        self.header = Some(header);
                         self.state = State::Root;
                         continue;
                     }
@@ -170,3 +201,48 @@ impl<'a> Parser<'a> {
         }
     }
 }
+
+/// Reads kanjidic2 incrementally, providing a [`Chunk`] for each part of the
+/// input.
+pub struct Reader<R> {
+    chunks: Chunks<R>,
+    started: bool,
+}
+
+impl<R> Reader<R>
+where
+    R: Read,
+{
+    pub fn new(reader: R) -> Self {
+        Self {
+            chunks: Chunks::new(reader, &["</header>", "</character>"]),
+            started: false,
+        }
+    }
+
+    /// Get the next chunk of input, or `None` if the input has been consumed.
+    pub fn next_chunk(&mut self) -> Result<Option<Chunk>> {
+        let Some(input) = self.chunks.next()? else {
+            return Ok(None);
+        };
+
+        Ok(Some(Chunk {
+            input: input.to_owned(),
+            started: mem::replace(&mut self.started, true),
+        }))
+    }
+}
+
+/// An owned chunk of input read by a [`Reader`], which can be parsed
+/// independently of other chunks.
+pub struct Chunk {
+    input: String,
+    started: bool,
+}
+
+impl Chunk {
+    /// Get a parser for the chunk.
+    pub fn parser(&self) -> Parser<'_> {
+        Parser::chunk(&self.input, self.started)
+    }
+}
@@ -0,0 +1,95 @@
+//! Parser for KRADFILE and RADKFILE, which decompose kanji into the visual
+//! components they are made up of.
+//!
+//! Both files are distributed in EUC-JP, so they need to be converted to UTF-8
+//! before being parsed.
+
+use anyhow::{bail, Result};
+
+/// Parse a KRADFILE or RADKFILE, calling `f` with every kanji and one of its
+/// components.
+///
+/// The format is detected from the content, where a RADKFILE lists kanji
+/// under `$ <component> <strokes>` headings and a KRADFILE has one `<kanji> :
+/// <component>...` line per kanji.
+pub fn parse<'a>(input: &'a str, mut f: impl FnMut(&'a str, &'a str)) -> Result<()> {
+    let mut component = None;
+
+    for (n, line) in input.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix('$') {
+            let Some(c) = rest.split_whitespace().next() else {
+                bail!("{}: missing component after `$`", n + 1);
+            };
+
+            component = Some(c);
+            continue;
+        }
+
+        if let Some(c) = component {
+            for (i, kanji) in line.char_indices() {
+                if !kanji.is_whitespace() {
+                    f(&line[i..i + kanji.len_utf8()], c);
+                }
+            }
+
+            continue;
+        }
+
+        let Some((kanji, components)) = line.split_once(':') else {
+            bail!("{}: expected `<kanji> : <components>`", n + 1);
+        };
+
+        let kanji = kanji.trim();
+
+        for c in components.split_whitespace() {
+            f(kanji, c);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    fn collect(input: &str) -> Vec<(&str, &str)> {
+        let mut output = Vec::new();
+        parse(input, |k, c| output.push((k, c))).unwrap();
+        output.sort();
+        output
+    }
+
+    #[test]
+    fn test_kradfile() {
+        let input = "# comment\n亜 : ｜ 一 口\n唖 : ｜ 一 口\n";
+
+        assert_eq!(
+            collect(input),
+            [
+                ("亜", "一"),
+                ("亜", "口"),
+                ("亜", "｜"),
+                ("唖", "一"),
+                ("唖", "口"),
+                ("唖", "｜"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_radkfile() {
+        let input = "# comment\n$ 一 1\n亜唖\n$ ｜ 1 js01\n亜\n唖\n";
+
+        assert_eq!(
+            collect(input),
+            [("亜", "一"), ("亜", "｜"), ("唖", "一"), ("唖", "｜")]
+        );
+    }
+}
@@ -4,17 +4,24 @@
 pub mod inflection;
 pub use self::inflection::{Form, Inflection, Inflections, OwnedInflections};
 
+mod chunks;
+
 mod concat;
 pub use self::concat::Concat;
 
+pub mod accent;
+
 pub mod jmdict;
+pub mod jmnedict;
 pub mod kanjidic2;
+pub mod kradfile;
+pub mod tatoeba;
 
 pub mod entities;
 pub use self::entities::PartOfSpeech;
 
 mod furigana;
-pub use self::furigana::{Furigana, FuriganaGroup};
+pub use self::furigana::{Alignment, Furigana, FuriganaGroup, OwnedAlignment};
 
 pub mod romaji;
 
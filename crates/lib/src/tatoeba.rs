@@ -0,0 +1,184 @@
+//! Parser for the Tatoeba (Tanaka corpus) example sentences as distributed by
+//! the WWWJDIC project.
+//!
+//! Each example consists of an `A:` line with the Japanese and English
+//! sentence, followed by a `B:` line listing the dictionary words used in the
+//! sentence.
+
+use anyhow::{bail, Result};
+use musli::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+/// A single example sentence.
+#[borrowme::borrowme]
+#[derive(Clone, Debug, Serialize, Deserialize, Encode, Decode)]
+#[musli(packed)]
+pub struct Sentence<'a> {
+    /// Identifier of the sentence pair, such as `4851_1`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<&'a str>,
+    /// The Japanese sentence.
+    pub japanese: &'a str,
+    /// The English translation.
+    pub english: &'a str,
+    /// Dictionary words used in the sentence.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[borrowed_attr(serde(borrow))]
+    pub words: Vec<Word<'a>>,
+}
+
+/// A word from the `B:` line of an example.
+#[borrowme::borrowme]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+#[musli(packed)]
+pub struct Word<'a> {
+    /// The dictionary form of the word.
+    pub headword: &'a str,
+    /// Reading used to disambiguate the headword, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reading: Option<&'a str>,
+    /// The sense of the dictionary entry which is illustrated, starting at 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sense: Option<u8>,
+    /// The form the word has in the sentence, if it differs from the
+    /// headword.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form: Option<&'a str>,
+    /// The sentence has been checked to be a good example of the word.
+    pub checked: bool,
+}
+
+impl<'a> Word<'a> {
+    /// Parse a single word such as `彼(かれ)[01]{彼の}~`.
+    fn parse(mut input: &'a str) -> Self {
+        let checked = if let Some(rest) = input.strip_suffix('~') {
+            input = rest;
+            true
+        } else {
+            false
+        };
+
+        let form = strip_group(&mut input, '{', '}');
+        let sense = strip_group(&mut input, '[', ']').and_then(|s| s.parse().ok());
+        let reading = strip_group(&mut input, '(', ')');
+
+        Self {
+            headword: input,
+            reading,
+            sense,
+            form,
+            checked,
+        }
+    }
+}
+
+/// Strip a trailing group delimited by `open` and `close` from the input.
+fn strip_group<'a>(input: &mut &'a str, open: char, close: char) -> Option<&'a str> {
+    let rest = input.strip_suffix(close)?;
+    let (head, group) = rest.rsplit_once(open)?;
+    *input = head;
+    Some(group)
+}
+
+/// Parse examples, calling `f` with every sentence.
+pub fn parse<'a>(input: &'a str, mut f: impl FnMut(Sentence<'a>)) -> Result<()> {
+    let mut current = None;
+
+    for (n, line) in input.lines().enumerate() {
+        let line = line.trim_end();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("A:") {
+            if let Some(sentence) = current.take() {
+                f(sentence);
+            }
+
+            let (japanese, rest) = rest.trim_start().split_once('\t').unwrap_or((rest, ""));
+
+            let (english, id) = match rest.split_once("#ID=") {
+                Some((english, id)) => (english, Some(id.trim())),
+                None => (rest, None),
+            };
+
+            current = Some(Sentence {
+                id,
+                japanese: japanese.trim(),
+                english: english.trim(),
+                words: Vec::new(),
+            });
+
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("B:") {
+            let Some(sentence) = &mut current else {
+                bail!("{}: `B:` line without a preceding `A:` line", n + 1);
+            };
+
+            sentence
+                .words
+                .extend(rest.split_whitespace().map(Word::parse));
+            continue;
+        }
+
+        bail!("{}: expected line starting with `A:` or `B:`", n + 1);
+    }
+
+    if let Some(sentence) = current.take() {
+        f(sentence);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let input =
+            "A: 彼は忙しい。\tHe is busy.#ID=303645_100000\nB: 彼(かれ)[01] は 忙しい{忙しい}~\n";
+
+        let mut output = Vec::new();
+        parse(input, |s| output.push(s)).unwrap();
+
+        let [sentence] = &output[..] else {
+            panic!("expected one sentence");
+        };
+
+        assert_eq!(sentence.id, Some("303645_100000"));
+        assert_eq!(sentence.japanese, "彼は忙しい。");
+        assert_eq!(sentence.english, "He is busy.");
+
+        assert_eq!(
+            sentence.words,
+            [
+                Word {
+                    headword: "彼",
+                    reading: Some("かれ"),
+                    sense: Some(1),
+                    form: None,
+                    checked: false,
+                },
+                Word {
+                    headword: "は",
+                    reading: None,
+                    sense: None,
+                    form: None,
+                    checked: false,
+                },
+                Word {
+                    headword: "忙しい",
+                    reading: None,
+                    sense: None,
+                    form: Some("忙しい"),
+                    checked: true,
+                },
+            ]
+        );
+    }
+}
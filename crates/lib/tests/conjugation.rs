@@ -0,0 +1,307 @@
+//! Conformance tests for conjugation, driven by the table in
+//! `fixtures/conjugation.txt`.
+//!
+//! Each line in the table consists of the class of the word as a JMdict part
+//! of speech, its kanji (or `-` if it is written in kana only), its reading,
+//! the inflection and the expected kanji and reading output. The word is
+//! conjugated and the whole table produced for it is compared with the
+//! expected one. Colloquial contractions are marked with a leading `~` in
+//! the inflection column.
+//!
+//! To add a word, add a line containing only its class, kanji and reading and
+//! run the tests with `JPV_BLESS=1`, which rewrites the table with the current
+//! output. Review the resulting diff carefully.
+//!
+//! Since the generated table is easy to bless by mistake, a few hand-written
+//! rows for each class are also kept in `fixtures/conjugation-reference.txt`
+//! and are checked against the output separately.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+use lib::inflection;
+use lib::jmdict;
+
+const TABLE: &str = "tests/fixtures/conjugation.txt";
+const REFERENCE: &str = "tests/fixtures/conjugation-reference.txt";
+
+/// A word being conjugated.
+#[derive(PartialEq, Eq)]
+struct Subject<'a> {
+    class: &'a str,
+    kanji: &'a str,
+    reading: &'a str,
+}
+
+/// Parse the table at `path`, returning each subject together with its
+/// expected lines.
+///
+/// The lines of a subject have to be kept together, so a subject which is
+/// repeated after another one is an error.
+fn parse<'a>(path: &str, table: &'a str) -> (String, Vec<(Subject<'a>, Vec<&'a str>)>) {
+    let mut header = String::new();
+    let mut subjects = Vec::<(Subject<'_>, Vec<&str>)>::new();
+
+    for (n, line) in table.lines().enumerate() {
+        if line.starts_with('#') {
+            if subjects.is_empty() {
+                header.push_str(line);
+                header.push('\n');
+            }
+
+            continue;
+        }
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut columns = line.split_whitespace();
+
+        let (Some(class), Some(kanji), Some(reading)) =
+            (columns.next(), columns.next(), columns.next())
+        else {
+            panic!("{path}:{}: expected class, kanji and reading", n + 1);
+        };
+
+        let subject = Subject {
+            class,
+            kanji,
+            reading,
+        };
+
+        let has_row = columns.next().is_some();
+
+        let lines = match subjects.last_mut() {
+            Some((last, lines)) if *last == subject => lines,
+            _ => {
+                if subjects.iter().any(|(s, _)| *s == subject) {
+                    panic!(
+                        "{path}:{}: repeated subject {class} {kanji} {reading}",
+                        n + 1
+                    );
+                }
+
+                subjects.push((subject, Vec::new()));
+                &mut subjects.last_mut().unwrap().1
+            }
+        };
+
+        if has_row {
+            lines.push(line);
+        }
+    }
+
+    (header, subjects)
+}
+
+/// Conjugate the given subject, producing lines in the format of the table.
+fn conjugate(subject: &Subject<'_>) -> Vec<String> {
+    let mut xml = String::from("<JMdict><entry><ent_seq>1</ent_seq>");
+
+    if subject.kanji != "-" {
+        write!(xml, "<k_ele><keb>{}</keb></k_ele>", subject.kanji).unwrap();
+    }
+
+    write!(
+        xml,
+        "<r_ele><reb>{}</reb></r_ele><sense><pos>&{};</pos><gloss>-</gloss></sense></entry></JMdict>",
+        subject.reading, subject.class
+    )
+    .unwrap();
+
+    let mut parser = jmdict::Parser::new(&xml);
+    let entry = parser.parse().unwrap().expect("missing entry");
+
+    let mut lines = Vec::new();
+
+    for (_, inflections, _) in inflection::conjugate(&entry) {
+        let all = inflections
+            .iter()
+            .map(|(i, f)| (i, f, ""))
+            .chain(inflections.iter_colloquial().map(|(i, f)| (i, f, "~")));
+
+        for (inflection, fragments, marker) in all {
+            let forms = inflection
+                .iter()
+                .map(|form| format!("{form:?}"))
+                .collect::<Vec<_>>();
+
+            let forms = if forms.is_empty() {
+                String::from("-")
+            } else {
+                forms.join("+")
+            };
+
+            let forms = format!("{marker}{forms}");
+
+            let furigana = fragments.furigana();
+            let kanji = furigana.kanji();
+            let reading = furigana.reading();
+
+            lines.push(format!(
+                "{} {} {} {forms} {kanji} {reading}",
+                subject.class, subject.kanji, subject.reading
+            ));
+        }
+    }
+
+    lines
+}
+
+/// Normalize a line so that the order of the forms in its inflection column
+/// doesn't matter.
+fn normalize(line: &str) -> String {
+    let mut columns = line.split_whitespace().collect::<Vec<_>>();
+
+    let Some(inflection) = columns.get_mut(3) else {
+        return columns.join(" ");
+    };
+
+    let (marker, forms) = match inflection.strip_prefix('~') {
+        Some(forms) => ("~", forms),
+        None => ("", *inflection),
+    };
+
+    let mut forms = forms.split('+').collect::<Vec<_>>();
+    forms.sort();
+    let forms = format!("{marker}{}", forms.join("+"));
+    columns[3] = &forms;
+    columns.join(" ")
+}
+
+/// Produce a line-based diff between the expected and actual tables.
+fn diff(expected: &[&str], actual: &[&str]) -> String {
+    let (n, m) = (expected.len(), actual.len());
+
+    // Longest common subsequence of every pair of suffixes.
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < n || j < m {
+        if i < n && j < m && expected[i] == actual[j] {
+            writeln!(out, "  {}", expected[i]).unwrap();
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            writeln!(out, "+ {}", actual[j]).unwrap();
+            j += 1;
+        } else {
+            writeln!(out, "- {}", expected[i]).unwrap();
+            i += 1;
+        }
+    }
+
+    out
+}
+
+#[test]
+fn test_conjugation_table() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TABLE);
+    let table = fs::read_to_string(&path).unwrap();
+    let (header, subjects) = parse(TABLE, &table);
+
+    assert!(!subjects.is_empty(), "{TABLE}: no words to conjugate");
+
+    if env::var_os("JPV_BLESS").is_some() {
+        let mut out = header;
+
+        for (subject, _) in &subjects {
+            out.push('\n');
+
+            for line in conjugate(subject) {
+                out.push_str(&line);
+                out.push('\n');
+            }
+        }
+
+        fs::write(&path, out).unwrap();
+        return;
+    }
+
+    let mut failures = String::new();
+
+    for (subject, expected) in &subjects {
+        let actual = conjugate(subject);
+        let actual = actual.iter().map(String::as_str).collect::<Vec<_>>();
+
+        if *expected != actual {
+            writeln!(
+                failures,
+                "{} {} {}:",
+                subject.class, subject.kanji, subject.reading
+            )
+            .unwrap();
+            failures.push_str(&diff(expected, &actual));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "conjugation differs from {TABLE}, rerun with JPV_BLESS=1 to update it:\n{failures}"
+    );
+}
+
+#[test]
+fn test_conjugation_reference() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(REFERENCE);
+    let table = fs::read_to_string(path).unwrap();
+    let (_, subjects) = parse(REFERENCE, &table);
+
+    assert!(!subjects.is_empty(), "{REFERENCE}: no words to conjugate");
+
+    let mut failures = String::new();
+
+    for (subject, expected) in &subjects {
+        let actual = conjugate(subject)
+            .iter()
+            .map(|line| normalize(line))
+            .collect::<Vec<_>>();
+
+        for line in expected {
+            if !actual.contains(&normalize(line)) {
+                writeln!(failures, "{line}").unwrap();
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "rows from {REFERENCE} missing from the conjugations:\n{failures}"
+    );
+}
+
+#[test]
+fn test_unconjugated_copula() {
+    // Only だ and です are conjugated as copulas.
+    let subject = Subject {
+        class: "cop",
+        kanji: "-",
+        reading: "である",
+    };
+
+    assert!(conjugate(&subject).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "test.txt:3: repeated subject v1 食べる たべる")]
+fn test_repeated_subject() {
+    parse(
+        "test.txt",
+        "v1 食べる たべる\nv5k 書く かく\nv1 食べる たべる\n",
+    );
+}
@@ -0,0 +1,507 @@
+//! End-to-end tests which build a database from the fixtures and query it.
+
+use std::collections::BTreeSet;
+use std::io::{self, Read};
+
+use lib::database::{self, Database, Entry, IndexSource, Input};
+use lib::entities::NameType;
+use lib::Form;
+use musli_zerocopy::OwnedBuf;
+
+const JMDICT: &str = include_str!("fixtures/jmdict.xml");
+const KANJIDIC2: &str = include_str!("fixtures/kanjidic2.xml");
+const JMNEDICT: &str = include_str!("fixtures/jmnedict.xml");
+const KRADFILE: &str = "食 : 人 良\n書 : 日 聿\n";
+const TATOEBA: &str = "A: 昼ご飯を食べる。\tI eat lunch.#ID=1_2\nB: 昼ご飯 を 食べる[01]~\nA: 学校で書く。\tI write at school.#ID=3_4\nB: 学校 で 書く\n";
+
+fn load() -> OwnedBuf {
+    database::load(Input {
+        jmdict: Some(Box::new(JMDICT.as_bytes())),
+        kanjidic2: Some(Box::new(KANJIDIC2.as_bytes())),
+        kradfile: Vec::new(),
+        accent: None,
+        jmnedict: Some(Box::new(JMNEDICT.as_bytes())),
+        tatoeba: None,
+        built: Some(0),
+        spill_threshold: None,
+    })
+    .unwrap()
+}
+
+/// A reader which produces at most the given number of bytes at a time, which
+/// splits the input into many small chunks.
+struct Trickle<'a>(&'a [u8], usize);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.1);
+        self.0.read(&mut buf[..n])
+    }
+}
+
+/// Build a database from every fixture, reading at most `block` bytes at a
+/// time and spilling index keys to disk according to `spill_threshold`.
+fn build(block: usize, spill_threshold: Option<usize>) -> Vec<u8> {
+    let buf = database::load(Input {
+        jmdict: Some(Box::new(Trickle(JMDICT.as_bytes(), block))),
+        kanjidic2: Some(Box::new(Trickle(KANJIDIC2.as_bytes(), block))),
+        kradfile: vec![KRADFILE],
+        accent: None,
+        jmnedict: Some(Box::new(Trickle(JMNEDICT.as_bytes(), block))),
+        tatoeba: Some(TATOEBA),
+        built: Some(1700000000),
+        spill_threshold,
+    })
+    .unwrap();
+
+    buf.as_slice().to_vec()
+}
+
+/// Search for the given input, returning the sequence numbers of matching
+/// entries in ranked order.
+fn search(db: &Database<'_>, input: &str) -> Vec<u64> {
+    let search = db.search(input, 10).unwrap();
+    search.entries.iter().map(|(_, e)| e.sequence).collect()
+}
+
+/// Search for the given input, returning the sequence numbers of matching
+/// names.
+fn names(db: &Database<'_>, input: &str) -> Vec<u64> {
+    let search = db.search(input, 10).unwrap();
+    search.names.iter().map(|e| e.sequence).collect()
+}
+
+/// Search for the given input, returning the literals of populated kanji.
+fn characters<'a>(db: &Database<'a>, input: &str) -> Vec<&'a str> {
+    let search = db.search(input, 10).unwrap();
+    search.characters.iter().map(|c| c.literal).collect()
+}
+
+/// Test if any of the sources refers to an inflection with the given form.
+fn has_form(sources: &BTreeSet<IndexSource>, form: Form) -> bool {
+    sources.iter().any(|source| match source {
+        IndexSource::VerbInflection { inflection, .. }
+        | IndexSource::AdjectiveInflection { inflection, .. }
+        | IndexSource::SuruInflection { inflection, .. } => inflection.contains(form),
+        _ => false,
+    })
+}
+
+#[test]
+fn test_parse_fixtures() {
+    let mut parser = lib::jmdict::Parser::new(JMDICT);
+    let mut count = 0;
+
+    while let Some(entry) = parser.parse().unwrap() {
+        assert!(!entry.reading_elements.is_empty(), "{}", entry.sequence);
+        assert!(!entry.senses.is_empty(), "{}", entry.sequence);
+        count += 1;
+    }
+
+    assert_eq!(count, 35);
+    assert_eq!(parser.created(), Some("2023-10-18"));
+
+    let mut parser = lib::kanjidic2::Parser::new(KANJIDIC2);
+    let mut literals = Vec::new();
+
+    while let Some(c) = parser.parse().unwrap() {
+        literals.push(c.literal);
+    }
+
+    assert_eq!(
+        literals,
+        ["食", "飲", "見", "書", "学", "校", "日", "本", "水", "高", "猫"]
+    );
+
+    let header = parser.header().unwrap();
+    assert_eq!(header.database_version, "2023-292");
+}
+
+#[test]
+fn test_parse_jmnedict() {
+    let mut parser = lib::jmnedict::Parser::new(JMNEDICT);
+    let mut entries = Vec::new();
+
+    while let Some(entry) = parser.parse().unwrap() {
+        entries.push(entry);
+    }
+
+    let sequences = entries.iter().map(|e| e.sequence).collect::<Vec<_>>();
+    assert_eq!(sequences, [5000001, 5000002, 5000003, 5000004, 5000005]);
+
+    let nakajima = &entries[1];
+    let kanji = nakajima.kanji_elements.iter().map(|k| k.text);
+    assert!(kanji.eq(["中島", "中嶋"]));
+    let readings = nakajima.reading_elements.iter().map(|r| r.text);
+    assert!(readings.eq(["なかじま", "なかしま"]));
+
+    let [translation] = nakajima.translations.as_slice() else {
+        panic!("expected a single translation");
+    };
+
+    assert!(translation.name_type.contains(NameType::Surname));
+    assert!(translation.name_type.contains(NameType::Place));
+    assert_eq!(translation.text, ["Nakajima", "Nakashima"]);
+
+    // Names without kanji only have readings.
+    assert!(entries[4].kanji_elements.is_empty());
+}
+
+#[test]
+fn test_search_names() {
+    let buf = load();
+    let db = Database::new(buf.as_slice()).unwrap();
+
+    assert_eq!(names(&db, "山田"), [5000001]);
+    assert_eq!(names(&db, "なかしま"), [5000002]);
+    assert_eq!(names(&db, "中嶋"), [5000002]);
+    assert_eq!(names(&db, "ジョン"), [5000005]);
+    assert_eq!(names(&db, "Yamada"), [5000001]);
+
+    // Names are returned separately from dictionary entries with the same
+    // spelling.
+    assert_eq!(names(&db, "日本"), [5000004]);
+    assert!(search(&db, "日本").contains(&1582710));
+    assert!(search(&db, "山田").is_empty());
+}
+
+#[test]
+fn test_search_ranking() {
+    let buf = load();
+    let db = Database::new(buf.as_slice()).unwrap();
+
+    // Exact kanji matches are ranked first.
+    assert_eq!(search(&db, "食べる"), [1358280]);
+    assert_eq!(search(&db, "日本語"), [1464530]);
+
+    // Homophones are ranked by priority, then by number of senses.
+    assert_eq!(search(&db, "かく"), [1207610, 1199330, 1229410]);
+
+    // English matches.
+    assert_eq!(search(&db, "school"), [1206730]);
+    assert_eq!(search(&db, "eat"), [1356480, 1358280]);
+
+    // Typos in english are tolerated.
+    assert_eq!(search(&db, "recieve"), [1330200]);
+
+    // Prefix matches.
+    assert_eq!(search(&db, "たべ"), [1358280]);
+}
+
+#[test]
+fn test_search_prefix() {
+    let buf = load();
+    let db = Database::new(buf.as_slice()).unwrap();
+
+    // Entries which only start with the input are marked as such, and
+    // exact matches are marked as words.
+    let search = db.search("にほん", 10).unwrap();
+
+    let sources = |sequence: u64| {
+        let (key, _) = search
+            .entries
+            .iter()
+            .find(|(_, e)| e.sequence == sequence)
+            .unwrap();
+        key.sources.clone()
+    };
+
+    assert!(sources(1582710).contains(&IndexSource::Word));
+    assert_eq!(
+        sources(1464530).into_iter().collect::<Vec<_>>(),
+        [IndexSource::Prefix]
+    );
+
+    // Prefix matches are ranked after all other matches, so that they never
+    // cause other matches to be truncated.
+    for input in ["にほん", "日本"] {
+        let search = db.search(input, 10).unwrap();
+
+        let prefix = search
+            .entries
+            .iter()
+            .map(|(key, _)| key.sources.iter().all(|s| *s == IndexSource::Prefix))
+            .collect::<Vec<_>>();
+
+        assert!(prefix.contains(&true), "{input}: {prefix:?}");
+        assert!(
+            prefix.windows(2).all(|w| w[0] <= w[1]),
+            "{input}: {prefix:?}"
+        );
+    }
+}
+
+#[test]
+fn test_search_inflections() {
+    let buf = load();
+    let db = Database::new(buf.as_slice()).unwrap();
+
+    let cases = [
+        ("食べた", 1358280, Form::Past),
+        ("書かない", 1199330, Form::Negative),
+        ("行った", 1578850, Form::Past),
+        ("来ない", 1547720, Form::Negative),
+        ("高くない", 1280640, Form::Negative),
+        ("書かされた", 1199330, Form::Causative),
+        ("話しながら", 1468180, Form::Nagara),
+        ("飲みすぎた", 1169870, Form::Sugiru),
+        ("高ければ", 1280640, Form::Hypothetical),
+        ("よくなかった", 2820690, Form::Negative),
+        ("良さそう", 2820690, Form::Sou),
+        ("静かな", 1371010, Form::Attributive),
+        ("静かじゃなかった", 1371010, Form::Negative),
+        ("食べさせたくなかった", 1358280, Form::Tai),
+        ("行かせたがっている", 1578850, Form::Tagaru),
+        ("お書きになりました", 1199330, Form::Honorific),
+        ("お待ちします", 1421720, Form::Humble),
+        ("食べてる", 1358280, Form::TeIru),
+        ("食べれる", 1358280, Form::Potential),
+    ];
+
+    for (input, sequence, form) in cases {
+        let search = db.search(input, 10).unwrap();
+
+        let Some((key, _)) = search.entries.iter().find(|(_, e)| e.sequence == sequence) else {
+            panic!("{input}: missing {sequence}");
+        };
+
+        assert!(has_form(&key.sources, form), "{input}: {:?}", key.sources);
+    }
+}
+
+#[test]
+fn test_search_glob() {
+    let buf = load();
+    let db = Database::new(buf.as_slice()).unwrap();
+
+    let results = search(&db, "食*");
+    assert!(results.contains(&1358280));
+    assert!(results.contains(&1356480));
+
+    assert_eq!(search(&db, "*べる"), [1358280]);
+    assert_eq!(search(&db, "た？る"), [1358280]);
+
+    // Wildcards in english input are left to the english search.
+    assert_eq!(search(&db, "school?"), search(&db, "school"));
+    assert!(!search(&db, "school?").is_empty());
+
+    // Patterns without a literal prefix or suffix are rejected.
+    assert!(db.search("?ん?", 10).is_err());
+    assert!(db.glob("*", 10).is_err());
+}
+
+#[test]
+fn test_search_suru() {
+    let buf = load();
+    let db = Database::new(buf.as_slice()).unwrap();
+
+    for input in ["勉強した", "勉強させられる", "べんきょうしている"] {
+        let search = db.search(input, 10).unwrap();
+
+        let Some((key, _)) = search.entries.iter().find(|(_, e)| e.sequence == 1380850) else {
+            panic!("{input}: missing 1380850");
+        };
+
+        assert!(
+            key.sources
+                .iter()
+                .any(|source| matches!(source, IndexSource::SuruInflection { .. })),
+            "{input}: {:?}",
+            key.sources
+        );
+    }
+}
+
+#[test]
+fn test_inflections_are_deinflected() {
+    let buf = load();
+    let db = Database::new(buf.as_slice()).unwrap();
+
+    // Inflected forms aren't stored in the index, but are resolved by
+    // deinflecting the query.
+    for (input, sequence) in [("食べなかった", 1358280), ("行きます", 1578850)] {
+        assert!(db.lookup(input).unwrap().is_empty(), "{input}: indexed");
+        assert_eq!(search(&db, input), [sequence], "{input}");
+
+        let ids = db.deinflect(input).unwrap();
+        assert!(
+            ids.iter().all(|id| id.source().is_inflection()),
+            "{input}: {ids:?}"
+        );
+    }
+
+    let analyze = |input: &str| -> Vec<String> { db.analyze(input, 0).into_values().collect() };
+    assert_eq!(analyze("行きます"), ["行きます", "行き"]);
+}
+
+#[test]
+fn test_resolve() {
+    let buf = load();
+    let db = Database::new(buf.as_slice()).unwrap();
+
+    let ids = db.resolve("食べる").unwrap();
+    assert!(ids.iter().any(|id| id.source() == IndexSource::Word));
+
+    // Inflected input resolves to its dictionary form, along with an
+    // explanation of how it was inflected.
+    let ids = db.resolve("食べた").unwrap();
+    assert!(!ids.is_empty());
+
+    for id in ids {
+        let Entry::Dict(entry) = db.get(id).unwrap() else {
+            panic!("expected a dictionary entry");
+        };
+
+        assert_eq!(entry.sequence, 1358280);
+        assert!(id.source().explain().is_some());
+    }
+
+    assert!(db.resolve("食べたい人").unwrap().is_empty());
+}
+
+#[test]
+fn test_populate_kanji() {
+    let buf = load();
+    let db = Database::new(buf.as_slice()).unwrap();
+
+    assert_eq!(characters(&db, "食べた"), ["食"]);
+    assert_eq!(characters(&db, "日本語"), ["日", "本"]);
+
+    // Kanji are populated from the kanji elements of matching entries.
+    assert_eq!(characters(&db, "school"), ["校", "学"]);
+
+    // Kanji which are missing from kanjidic2 are skipped.
+    assert!(characters(&db, "行く").is_empty());
+
+    let kanji = db.search("た.べる", 10).unwrap().characters;
+    assert!(kanji.iter().any(|c| c.literal == "食"));
+}
+
+#[test]
+fn test_analyze() {
+    let buf = load();
+    let db = Database::new(buf.as_slice()).unwrap();
+
+    let analyze = |input: &str, start: usize| -> Vec<String> {
+        db.analyze(input, start).into_values().collect()
+    };
+
+    let input = "昼ご飯を食べた";
+
+    assert_eq!(analyze(input, 0), ["昼ご飯"]);
+    assert_eq!(analyze(input, "昼".len()), ["ご飯"]);
+    assert_eq!(analyze(input, "昼ご飯".len()), ["を"]);
+    assert_eq!(analyze(input, "昼ご飯を".len()), ["食べた", "食べ"]);
+
+    // Longer matches are ranked first.
+    let input = "私は日本語を勉強します";
+    assert_eq!(analyze(input, "私は".len()), ["日本語", "日本"]);
+}
+
+#[test]
+fn test_segment() {
+    let buf = load();
+    let db = Database::new(buf.as_slice()).unwrap();
+
+    let segment = |input: &str| -> Vec<(String, Option<u64>)> {
+        let tokens = db.segment(input).unwrap();
+
+        tokens
+            .into_iter()
+            .map(|t| {
+                let text = input[t.start..t.end].to_owned();
+                (text, t.entry.map(|(_, e)| e.sequence))
+            })
+            .collect()
+    };
+
+    let token = |text: &str, sequence: Option<u64>| (text.to_owned(), sequence);
+
+    // Kana and kanji mixed in the same word.
+    assert_eq!(
+        segment("昼ご飯を食べた"),
+        [
+            token("昼ご飯", Some(1589490)),
+            token("を", Some(2029010)),
+            token("食べた", Some(1358280)),
+        ]
+    );
+
+    // Inflected verbs are matched as a whole through deinflection.
+    let tokens = db.segment("日本語を書かなかった").unwrap();
+    let [.., last] = tokens.as_slice() else {
+        panic!("no tokens");
+    };
+    let (key, entry) = last.entry.as_ref().unwrap();
+    assert_eq!(entry.sequence, 1199330);
+    assert!(has_form(&key.sources, Form::Negative));
+    assert!(has_form(&key.sources, Form::Past));
+
+    // Unknown spans are grouped into a single token without an entry.
+    assert_eq!(
+        segment("ペンで手紙を書く"),
+        [
+            token("ペン", None),
+            token("で", Some(2028980)),
+            token("手紙", None),
+            token("を", Some(2029010)),
+            token("書く", Some(1199330)),
+        ]
+    );
+}
+
+#[test]
+fn test_furigana_alignment() {
+    let buf = load();
+    let db = Database::new(buf.as_slice()).unwrap();
+
+    let alignment = |input: &str, sequence: u64| -> Vec<u8> {
+        let search = db.search(input, 10).unwrap();
+
+        let Some((_, entry)) = search.entries.iter().find(|(_, e)| e.sequence == sequence) else {
+            panic!("{input}: missing {sequence}");
+        };
+
+        entry.reading_elements[0].alignment(input).to_vec()
+    };
+
+    assert_eq!(alignment("学校", 1206730), [2, 2]);
+
+    // 日本 isn't read according to the readings of its kanji.
+    assert!(alignment("日本", 1582710).is_empty());
+}
+
+#[test]
+fn test_verify() {
+    let buf = load();
+    Database::new(buf.as_slice()).unwrap().verify().unwrap();
+
+    // Corruption is only detected when verifying, including corruption of the
+    // header.
+    for offset in [12, buf.len() / 2] {
+        let mut data = buf.as_slice().to_vec();
+        data[offset] ^= 0xff;
+
+        let db = Database::new(&data).unwrap();
+        assert!(db.verify().is_err(), "corruption at {offset}");
+    }
+}
+
+#[test]
+fn test_reproducible() {
+    // Small reads and a tiny spill threshold make sure that the input is
+    // processed in many chunks and that keys are merged from disk.
+    let a = build(1024, Some(4));
+    let b = build(1024, Some(4));
+    assert!(a == b, "builds from the same input differ");
+
+    let c = build(usize::MAX, None);
+    assert!(a == c, "builds differ depending on chunking and spilling");
+
+    let db = Database::new(&a).unwrap();
+    db.verify().unwrap();
+    assert_eq!(db.info().unwrap().built, 1700000000);
+    assert!(!db.deinflect("食べた").unwrap().is_empty());
+    assert_eq!(db.examples_for(1358280).unwrap().len(), 1);
+    assert_eq!(names(&db, "山田"), [5000001]);
+}
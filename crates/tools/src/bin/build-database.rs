@@ -1,23 +1,47 @@
-use std::fs;
-use std::fs::File;
-use std::io::Read;
+use std::env;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use anyhow::{anyhow, Context, Result};
 use clap::Parser;
+use encoding_rs::EUC_JP;
 use flate2::read::GzDecoder;
 use lib::database::{self};
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::EnvFilter;
 
+/// Magic bytes at the start of a gzip file.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
 #[derive(Parser)]
 struct Args {
     /// Output directory.
     #[arg(long)]
     out: Option<PathBuf>,
-    /// Path to load dictionary from. Defaults to `JMdict_e_examp.gz`.
-    path: Option<PathBuf>,
+    /// Path to load JMdict from. If no source at all is specified, defaults
+    /// to `JMdict_e_examp.gz`.
+    #[arg(long)]
+    jmdict: Option<PathBuf>,
+    /// Path to load kanjidic2 from. If no source at all is specified,
+    /// defaults to `kanjidic2.xml.gz`.
+    #[arg(long)]
+    kanjidic2: Option<PathBuf>,
+    /// Path to a KRADFILE or RADKFILE to load kanji components from. Can be
+    /// specified multiple times.
+    #[arg(long)]
+    kradfile: Vec<PathBuf>,
+    /// Path to tab-separated pitch accent data, with one `<kanji>\t<reading>\t<accents>`
+    /// entry per line.
+    #[arg(long)]
+    accent: Option<PathBuf>,
+    /// Path to load proper names from, such as `JMnedict.xml.gz`.
+    #[arg(long)]
+    jmnedict: Option<PathBuf>,
+    /// Path to load Tatoeba example sentences from, such as `examples.utf.gz`.
+    #[arg(long)]
+    tatoeba: Option<PathBuf>,
 }
 
 fn main() -> Result<()> {
@@ -36,21 +60,49 @@ fn main() -> Result<()> {
         .unwrap_or(Path::new("."))
         .join("database.bin");
 
-    let jmdict = args
-        .path
-        .as_deref()
-        .unwrap_or(Path::new("JMdict_e_examp.gz"));
+    // Only fall back to the default sources if none were specified, so that
+    // building from for example only `--jmnedict` doesn't pull them in.
+    let no_sources = args.jmdict.is_none()
+        && args.kanjidic2.is_none()
+        && args.kradfile.is_empty()
+        && args.accent.is_none()
+        && args.jmnedict.is_none()
+        && args.tatoeba.is_none();
 
-    let kanjidic2 = args
-        .path
-        .as_deref()
-        .unwrap_or(Path::new("kanjidic2.xml.gz"));
+    let (jmdict, kanjidic2) = if no_sources {
+        (
+            Some(PathBuf::from("JMdict_e_examp.gz")),
+            Some(PathBuf::from("kanjidic2.xml.gz")),
+        )
+    } else {
+        (args.jmdict, args.kanjidic2)
+    };
 
     let start = Instant::now();
 
-    let jmdict = load_file(jmdict).with_context(|| jmdict.display().to_string())?;
-    let kanjidic2 = load_file(kanjidic2).with_context(|| kanjidic2.display().to_string())?;
-    let data = database::load(&jmdict, &kanjidic2)?;
+    let jmdict = open_optional(jmdict.as_deref())?;
+    let kanjidic2 = open_optional(kanjidic2.as_deref())?;
+
+    let mut kradfile = Vec::new();
+
+    for path in &args.kradfile {
+        kradfile.push(load_euc_jp(path).with_context(|| path.display().to_string())?);
+    }
+
+    let accent = load_optional(args.accent.as_deref())?;
+    let jmnedict = open_optional(args.jmnedict.as_deref())?;
+    let tatoeba = load_optional(args.tatoeba.as_deref())?;
+
+    let data = database::load(database::Input {
+        jmdict,
+        kanjidic2,
+        kradfile: kradfile.iter().map(String::as_str).collect(),
+        accent: accent.as_deref(),
+        jmnedict,
+        tatoeba: tatoeba.as_deref(),
+        built: source_date_epoch()?,
+        spill_threshold: None,
+    })?;
 
     let duration = Instant::now().duration_since(start);
     tracing::info!(?duration);
@@ -60,10 +112,89 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Get the build timestamp from `SOURCE_DATE_EPOCH` if it is set, so that
+/// builds can be reproduced.
+///
+/// See <https://reproducible-builds.org/docs/source-date-epoch/>.
+fn source_date_epoch() -> Result<Option<u64>> {
+    let Some(value) = env::var_os("SOURCE_DATE_EPOCH") else {
+        return Ok(None);
+    };
+
+    let value = value
+        .to_str()
+        .and_then(|value| value.parse().ok())
+        .context("SOURCE_DATE_EPOCH: expected a unix timestamp")?;
+
+    Ok(Some(value))
+}
+
+/// Load a file which might be encoded in EUC-JP, which is the case for the
+/// KRADFILE and RADKFILE distributions.
+fn load_euc_jp(path: &Path) -> Result<String> {
+    let bytes = load_bytes(path)?;
+
+    match String::from_utf8(bytes) {
+        Ok(string) => Ok(string),
+        Err(error) => {
+            let (string, _, errors) = EUC_JP.decode(error.as_bytes());
+
+            if errors {
+                return Err(anyhow!("Not valid UTF-8 or EUC-JP"));
+            }
+
+            Ok(string.into_owned())
+        }
+    }
+}
+
+/// Open the file at the given path for incremental reading if it is
+/// specified.
+fn open_optional(path: Option<&Path>) -> Result<Option<Box<dyn Read>>> {
+    let Some(path) = path else {
+        return Ok(None);
+    };
+
+    let reader = open(path).with_context(|| path.display().to_string())?;
+    Ok(Some(reader))
+}
+
+/// Open a file, decompressing it if it starts with the gzip magic bytes.
+fn open(path: &Path) -> Result<Box<dyn Read>> {
+    let mut reader = BufReader::new(File::open(path)?);
+
+    if reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        return Ok(Box::new(GzDecoder::new(reader)));
+    }
+
+    Ok(Box::new(reader))
+}
+
+/// Load the file at the given path if it is specified.
+fn load_optional(path: Option<&Path>) -> Result<Option<String>> {
+    let Some(path) = path else {
+        return Ok(None);
+    };
+
+    let string = load_file(path).with_context(|| path.display().to_string())?;
+    Ok(Some(string))
+}
+
 fn load_file(path: &Path) -> Result<String> {
-    let input = File::open(path)?;
-    let mut input = GzDecoder::new(input);
-    let mut string = String::new();
-    input.read_to_string(&mut string)?;
-    Ok(string)
+    Ok(String::from_utf8(load_bytes(path)?)?)
+}
+
+/// Load the contents of a file, decompressing it if it starts with the gzip
+/// magic bytes.
+fn load_bytes(path: &Path) -> Result<Vec<u8>> {
+    let bytes = fs::read(path)?;
+
+    if !bytes.starts_with(&GZIP_MAGIC) {
+        return Ok(bytes);
+    }
+
+    let mut input = GzDecoder::new(&bytes[..]);
+    let mut output = Vec::new();
+    input.read_to_end(&mut output)?;
+    Ok(output)
 }
@@ -52,7 +52,9 @@ impl Combined {
 
     /// Provide furigana iterator for the combined reading.
     fn furigana(&self) -> Furigana<'_, 1, 1> {
-        kana::Full::new(&self.kanji.text, &self.reading.text, "").furigana()
+        kana::Full::new(&self.kanji.text, &self.reading.text, "")
+            .furigana()
+            .with_alignment(self.reading.alignment(&self.kanji.text))
     }
 
     /// Test if this contains the given text.
@@ -170,8 +172,17 @@ impl Component for Entry {
                 });
 
         let extras = inflections.clone().take(1).flat_map(
-            |(index, state, (kind, inflection, inflections))| {
-                render_extra(ctx, index, kind, inflection, inflections, state.filter)
+            |(index, state, (kind, inflection, reading, inflections))| {
+                let alignment = alignment(entry, reading);
+                render_extra(
+                    ctx,
+                    index,
+                    kind,
+                    inflection,
+                    inflections,
+                    alignment,
+                    state.filter,
+                )
             },
         );
 
@@ -228,7 +239,9 @@ impl Component for Entry {
             |iter| html!(<ul class="block list-numerical">{for iter}</ul>),
         );
 
-        let inflections = inflections.map(|(_, _, (_, _, i))| i).next();
+        let inflections = inflections
+            .map(|(_, _, (_, _, r, i))| (alignment(entry, r), i))
+            .next();
 
         let show_inflections = inflections.map(|_| {
             let onclick = ctx.link().callback(|_: MouseEvent| Msg::ToggleInflection);
@@ -246,24 +259,47 @@ impl Component for Entry {
             }
         });
 
-        let inflection = inflections.filter(|_| self.show_inflection).and_then(|inflections| {
-            let iter = inflections.inflections.iter().map(|(inflection, word)| {
+        let inflection = inflections.filter(|_| self.show_inflection).and_then(|(alignment, inflections)| {
+            let render = |(inflection, word): (&Inflection, &kana::OwnedFull)| {
                 html! {
                     <li class="section">
                         <div class="block">{format!("{inflection:?}")}</div>
-                        <div class="block text kanji highlight">{ruby(word.furigana())}</div>
+                        <div class="block text kanji highlight">{ruby(word.furigana().with_alignment(alignment))}</div>
                     </li>
                 }
+            };
+
+            // Honorific and humble forms are grouped separately, since they
+            // are used depending on who is being talked about.
+            let (keigo, plain) = inflections
+                .inflections
+                .iter()
+                .partition::<Vec<_>, _>(|(inflection, _)| inflection.is_keigo());
+
+            let plain = plain.into_iter().map(render);
+
+            let keigo = (!keigo.is_empty()).then(|| {
+                let iter = keigo.into_iter().map(render);
+
+                html! {
+                    <>
+                        <div class="block row">{"Honorific / humble"}</div>
+                        <ul class="block list-bulleted">{for iter}</ul>
+                    </>
+                }
             });
 
             Some(html! {
-                <ul class="block list-bulleted">
-                    <li class="section">
-                        <div class="block">{"Dictionary"}</div>
-                        <div class="block text kanji highlight">{ruby(inflections.dictionary.furigana())}</div>
-                    </li>
-                    {for iter}
-                </ul>
+                <>
+                    <ul class="block list-bulleted">
+                        <li class="section">
+                            <div class="block">{"Dictionary"}</div>
+                            <div class="block text kanji highlight">{ruby(inflections.dictionary.furigana().with_alignment(alignment))}</div>
+                        </li>
+                        {for plain}
+                    </ul>
+                    {for keigo}
+                </>
             })
         });
 
@@ -462,6 +498,7 @@ impl Entry {
 enum InflectionKind {
     Verb,
     Adjective,
+    Suru,
 }
 
 impl InflectionKind {
@@ -469,6 +506,7 @@ impl InflectionKind {
         match self {
             InflectionKind::Verb => "Conjugation:",
             InflectionKind::Adjective => "Inflection:",
+            InflectionKind::Suru => "Conjugation:",
         }
     }
 
@@ -476,6 +514,7 @@ impl InflectionKind {
         match self {
             InflectionKind::Verb => "Result based on verb conjugation",
             InflectionKind::Adjective => "Result based on adjective inflection",
+            InflectionKind::Suru => "Result based on する conjugation",
         }
     }
 }
@@ -484,43 +523,81 @@ impl InflectionKind {
 fn find_inflection<'a>(
     source: &IndexSource,
     inflections: &'a [(inflection::Reading, OwnedInflections)],
-) -> Option<(InflectionKind, Inflection, &'a OwnedInflections)> {
+) -> Option<(
+    InflectionKind,
+    Inflection,
+    inflection::Reading,
+    &'a OwnedInflections,
+)> {
     Some(match source {
         IndexSource::VerbInflection {
             reading,
             inflection,
+            ..
         } => {
             let Some((_, inflections)) = inflections.iter().find(|(r, _)| *r == *reading) else {
                 return None;
             };
 
-            (InflectionKind::Verb, *inflection, inflections)
+            (InflectionKind::Verb, *inflection, *reading, inflections)
         }
         IndexSource::AdjectiveInflection {
             reading,
             inflection,
+            ..
         } => {
             let Some((_, inflections)) = inflections.iter().find(|(r, _)| *r == *reading) else {
                 return None;
             };
 
-            (InflectionKind::Adjective, *inflection, inflections)
+            (
+                InflectionKind::Adjective,
+                *inflection,
+                *reading,
+                inflections,
+            )
+        }
+        IndexSource::SuruInflection {
+            reading,
+            inflection,
+            ..
+        } => {
+            let Some((_, inflections)) = inflections.iter().find(|(r, _)| *r == *reading) else {
+                return None;
+            };
+
+            (InflectionKind::Suru, *inflection, *reading, inflections)
         }
         _ => return None,
     })
 }
 
+/// Get the per-kanji furigana alignment of the reading an inflection table was
+/// generated from.
+fn alignment(entry: &jmdict::OwnedEntry, reading: inflection::Reading) -> &[u8] {
+    let Some(kanji) = entry.kanji_elements.get(usize::from(reading.kanji)) else {
+        return &[];
+    };
+
+    entry
+        .reading_elements
+        .get(usize::from(reading.reading))
+        .map(|r| r.alignment(&kanji.text))
+        .unwrap_or_default()
+}
+
 fn render_extra(
     ctx: &Context<Entry>,
     index: usize,
     kind: InflectionKind,
     inflection: Inflection,
     inflections: &OwnedInflections,
+    alignment: &[u8],
     filter: Inflection,
 ) -> Option<Html> {
     let word = inflections.get(inflection ^ filter);
 
-    let word = word.map(|w| ruby(w.furigana())).map(
+    let word = word.map(|w| ruby(w.furigana().with_alignment(alignment))).map(
         |word| html!(<div class="block row"><span class="text kanji highlight">{word}</span></div>),
     );
 